@@ -3,23 +3,94 @@ extern crate cbindgen;
 #[cfg(feature = "ffi")]
 use std::env;
 
-// TODO parse and optimize yaml files into serde binary for faster
-// startup time.
-
 fn main() {
-    // let mut build_dir = get_cargo_target_dir().unwrap();
-    // build_dir.push("regexes");
+    println!("cargo:rerun-if-changed=build.rs");
 
-    // let _ = std::fs::create_dir(build_dir);
+    #[cfg(feature = "precompiled")]
+    precompile_regexes();
 
-    // let contents = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/regexes/bots.yml"));
-    // let value: serde_yaml::Value = serde_yaml::from_str(contents).unwrap();
     #[cfg(feature = "ffi")]
     build_cpp_header();
 }
 
+/// Pre-parses every `regexes/**/*.yml` file into message pack, which the library then
+/// embeds and deserializes with the very same serde types used for yaml, instead of
+/// paying for yaml parsing on every cold start.
+#[cfg(feature = "precompiled")]
+fn precompile_regexes() {
+    use std::path::{Path, PathBuf};
+
+    fn yaml_files(dir: &Path, files: &mut Vec<PathBuf>) {
+        for entry in std::fs::read_dir(dir).expect("regexes directory") {
+            let path = entry.expect("regexes directory entry").path();
+            if path.is_dir() {
+                yaml_files(&path, files);
+            } else if path.extension().is_some_and(|ext| ext == "yml") {
+                files.push(path);
+            }
+        }
+    }
+
+    let regexes = Path::new(env!("CARGO_MANIFEST_DIR")).join("regexes");
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap()).join("regexes");
+
+    println!("cargo:rerun-if-changed={}", regexes.display());
+
+    let mut files = Vec::new();
+    yaml_files(&regexes, &mut files);
+
+    for file in files {
+        let relative = file.strip_prefix(&regexes).unwrap();
+
+        let contents = std::fs::read_to_string(&file).expect("readable yaml file");
+        let value: serde_yaml::Value = serde_yaml::from_str(&contents)
+            .unwrap_or_else(|err| panic!("invalid yaml in {}: {}", file.display(), err));
+        let value = normalize(value, false)
+            .unwrap_or_else(|err| panic!("unable to precompile {}: {}", file.display(), err));
+
+        let bytes = rmp_serde::to_vec(&value).expect("serializable yaml value");
+
+        #[cfg(feature = "compress")]
+        let bytes = miniz_oxide::deflate::compress_to_vec(&bytes, 9);
+
+        let out = out_dir.join(relative).with_extension("yml.bin");
+        std::fs::create_dir_all(out.parent().unwrap()).unwrap();
+        std::fs::write(&out, bytes).unwrap();
+    }
+}
+
+/// Message pack, unlike yaml, can't hand a number to a field expecting a string, so
+/// the only non string scalars we allow are integer keys (eg. brand `8848`), which are
+/// turned into the exact same string yaml would have produced. Anything else is
+/// rejected here rather than risking a database that detects differently.
+#[cfg(feature = "precompiled")]
+fn normalize(value: serde_yaml::Value, key: bool) -> Result<serde_yaml::Value, String> {
+    use serde_yaml::Value;
+
+    match value {
+        Value::Null | Value::String(_) => Ok(value),
+        Value::Number(number) if key && (number.is_i64() || number.is_u64()) => {
+            Ok(Value::String(number.to_string()))
+        }
+        Value::Sequence(values) => values
+            .into_iter()
+            .map(|value| normalize(value, false))
+            .collect::<Result<_, _>>()
+            .map(Value::Sequence),
+        Value::Mapping(mapping) => mapping
+            .into_iter()
+            .map(|(key, value)| Ok((normalize(key, true)?, normalize(value, false)?)))
+            .collect::<Result<_, String>>()
+            .map(Value::Mapping),
+        value => Err(format!("unsupported value {:?}, quote it", value)),
+    }
+}
+
 #[cfg(feature = "ffi")]
 fn build_cpp_header() {
+    // the header follows the sources, not just the rules.
+    println!("cargo:rerun-if-changed=src");
+
     let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
 
     let res = cbindgen::Builder::new().with_crate(crate_dir).generate();
@@ -37,22 +108,3 @@ fn build_cpp_header() {
         }
     }
 }
-
-// we can use this to maybe compress the yaml files into a more compact representation
-// before placing into the binary.
-#[allow(dead_code)]
-fn get_cargo_target_dir() -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
-    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR")?);
-    let profile = std::env::var("PROFILE")?;
-    let mut target_dir = None;
-    let mut sub_path = out_dir.as_path();
-    while let Some(parent) = sub_path.parent() {
-        if parent.ends_with(&profile) {
-            target_dir = Some(parent);
-            break;
-        }
-        sub_path = parent;
-    }
-    let target_dir = target_dir.ok_or("not found")?;
-    Ok(target_dir.to_path_buf())
-}
@@ -39,8 +39,28 @@ extern "C" {
 
 RDDDeviceDetector *rdd_device_detector_new(uint64_t cache_size);
 
+/// Loads the rule database from a directory laid out like upstream's `regexes/`
+/// directory. Returns null if the directory could not be loaded, see
+/// `rdd_last_error_code` and `rdd_last_error_message` for why.
+RDDDeviceDetector *rdd_device_detector_from_dir(const char *dir, uint64_t cache_size);
+
+/// The stable code of the error the last `rdd_device_detector_from_dir` on this thread
+/// failed with, or 0 if it succeeded.
+uint32_t rdd_last_error_code();
+
+/// The message of the error the last `rdd_device_detector_from_dir` on this thread failed
+/// with, or null if it succeeded.
+char *rdd_last_error_message();
+
 RDDDetection *rdd_lookup(const RDDDeviceDetector *rdd, const char *ua);
 
+/// The stable code of the error a lookup failed with, as given by `DetectError::code`,
+/// or 0 if it succeeded.
+uint32_t rdd_error_code(const RDDDetection *rdd);
+
+/// The message of the error a lookup failed with, or null if it succeeded.
+char *rdd_error_message(const RDDDetection *rdd);
+
 const RDDClient *rdd_client(const RDDDetection *rdd);
 
 char *rdd_client_name(const RDDClient *client);
@@ -53,6 +73,16 @@ char *rdd_client_browser_engine(const RDDClient *client);
 
 char *rdd_client_browser_version(const RDDClient *client);
 
+char *rdd_client_short_name(const RDDClient *client);
+
+char *rdd_client_url(const RDDClient *client);
+
+char *rdd_client_subtype(const RDDClient *client);
+
+char *rdd_client_browser_family(const RDDClient *client);
+
+bool rdd_client_mobile_only(const RDDClient *client);
+
 const RDDDevice *rdd_device(const RDDDetection *rdd);
 
 char *rdd_device_brand(const RDDDevice *device);
@@ -71,6 +101,8 @@ char *rdd_os_platform(const RDDOS *os);
 
 char *rdd_os_family(const RDDOS *os);
 
+char *rdd_os_short_name(const RDDOS *os);
+
 const RDDBot *rdd_bot(const RDDDetection *rdd);
 
 char *rdd_bot_name(const RDDBot *bot);
@@ -85,6 +117,14 @@ char *rdd_bot_producer_url(const RDDBot *bot);
 
 bool rdd_is_bot(const RDDDetection *rdd);
 
+/// What drives an automated browser, eg. `headless chrome`, null for browsers used by
+/// a person.
+char *rdd_automation(const RDDDetection *rdd);
+
+bool rdd_is_automated(const RDDDetection *rdd);
+
+bool rdd_is_reduced_ua(const RDDDetection *rdd);
+
 bool rdd_is_mobile(const RDDDetection *rdd);
 
 bool rdd_is_touch_enabled(const RDDDetection *rdd);
@@ -0,0 +1,153 @@
+//! Telling versions a user agent reports apart from those it was frozen at, see
+//! [`KnownDevice::version_accuracy`].
+//!
+//! Chrome's user agent reduction freezes all but the major version at `0.0.0`, Android
+//! at `Android 10; K` and macOS at `10_15_7`, and Windows 11 still says `NT 10.0`.
+//! Only client hints then carry the actual versions.
+//!
+//! [`KnownDevice::version_accuracy`]: crate::device_detector::KnownDevice::version_accuracy
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use crate::client_hints::ClientHint;
+use crate::error::Result;
+use crate::parsers::client::Client;
+use crate::parsers::oss::OS;
+use crate::parsers::utils::SafeRegex as Regex;
+
+static REDUCED_CHROME: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Chrome/\d+\.0\.0\.0").expect("reduced chrome regex"));
+// `Android 10; K`, or on later versions only the major version, eg. `Android 14; K`.
+static ANDROID_K: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(Android (1[0-6])[.\d]*; K)(?:\s+Build/|[;)])").expect("android k regex")
+});
+// only the major version, eg. `Android 14)`.
+static ANDROID_MAJOR: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(Android (1[0-6]))\)\s*AppleWebKit").expect("android major regex")
+});
+static FROZEN_MAC: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Mac OS X 10[_.]15[_.]7").expect("frozen mac regex"));
+static WINDOWS_10: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Windows NT 10\.0").expect("windows 10 regex"));
+
+/// Whether a version is the one the client runs, or one its user agent was frozen at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Accuracy {
+    Exact,
+    /// Only the major version, or a version standing in for several, eg. Windows 10
+    /// for Windows 11.
+    Approximate,
+}
+
+/// How accurate each version of a detection is, `None` for versions not detected.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct VersionAccuracy {
+    pub client: Option<Accuracy>,
+    pub engine: Option<Accuracy>,
+    pub os: Option<Accuracy>,
+}
+
+impl VersionAccuracy {
+    /// Whether every version detected is exact.
+    pub fn is_exact(&self) -> bool {
+        [self.client, self.engine, self.os]
+            .iter()
+            .all(|accuracy| *accuracy != Some(Accuracy::Approximate))
+    }
+}
+
+/// The part of a reduced Android user agent standing in for its version and model,
+/// see [`reduced_android`].
+pub(crate) struct ReducedAndroid<'a> {
+    /// Eg. `Android 10; K` or `Android 13`, which client hints restore in its place.
+    pub(crate) text: &'a str,
+    pub(crate) major: u32,
+    /// Whether the model is given as `K`, rather than left out.
+    pub(crate) k: bool,
+}
+
+impl ReducedAndroid<'_> {
+    /// Whether the user agent is frozen at `Android 10; K`, hiding the actual version.
+    pub(crate) fn is_android_10_k(&self) -> bool {
+        self.k && self.major == 10
+    }
+}
+
+/// The reduced part of an Android user agent, if it is one Chrome reduced.
+pub(crate) fn reduced_android(ua: &str) -> Result<Option<ReducedAndroid<'_>>> {
+    for (regex, k) in [(&ANDROID_K, true), (&ANDROID_MAJOR, false)] {
+        if let Some(captures) = regex.captures(ua)? {
+            return Ok(captures
+                .get(1)
+                .zip(captures.get(2))
+                .map(|(text, major)| ReducedAndroid {
+                    text: text.as_str(),
+                    major: major.as_str().parse().unwrap_or(10),
+                    k,
+                }));
+        }
+    }
+    Ok(None)
+}
+
+/// Whether the user agent is one of those Chrome reduces, or otherwise freezes versions
+/// at a value.
+pub(crate) fn is_reduced(ua: &str) -> Result<bool> {
+    Ok(
+        REDUCED_CHROME.is_match(ua)?
+            || reduced_android(ua)?.is_some()
+            || FROZEN_MAC.is_match(ua)?,
+    )
+}
+
+/// The accuracy of the versions detected from `ua`, those taken from client hints
+/// being exact.
+pub(crate) fn version_accuracy(
+    ua: &str,
+    client_hints: Option<&ClientHint>,
+    client: Option<&Client>,
+    os: Option<&OS>,
+) -> Result<VersionAccuracy> {
+    let reduced_chrome = REDUCED_CHROME.is_match(ua)?;
+    // `120.0.0.0`, as the hints give the full version.
+    let chrome = |version: &Option<String>| {
+        version.as_deref().map(|version| {
+            if reduced_chrome && version.ends_with(".0.0.0") {
+                Accuracy::Approximate
+            } else {
+                Accuracy::Exact
+            }
+        })
+    };
+
+    let hinted_os = client_hints.is_some_and(|hints| hints.platform_version.is_some());
+    let os_accuracy = match os {
+        Some(OS {
+            name,
+            version: Some(version),
+            ..
+        }) => {
+            let frozen = match name.as_str() {
+                "Windows" => version == "10" && WINDOWS_10.is_match(ua)?,
+                "Mac" => version == "10.15.7" && FROZEN_MAC.is_match(ua)?,
+                "Android" => reduced_android(ua)?.is_some(),
+                _ => false,
+            };
+
+            Some(if frozen && !hinted_os {
+                Accuracy::Approximate
+            } else {
+                Accuracy::Exact
+            })
+        }
+        _ => None,
+    };
+
+    Ok(VersionAccuracy {
+        client: client.and_then(|client| chrome(&client.version)),
+        engine: client.and_then(|client| chrome(&client.engine_version)),
+        os: os_accuracy,
+    })
+}
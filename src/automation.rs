@@ -0,0 +1,70 @@
+//! Telling browsers driven by automation, eg. headless Chrome or Selenium, apart from
+//! those used by people. Unlike bots, they mostly don't say who they are, and are
+//! still detected as the browser or library they are.
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use crate::client_hints::ClientHint;
+use crate::error::Result;
+use crate::parsers::utils::{static_user_agent_match, SafeRegex as Regex};
+
+/// What drives an automated browser, as given away by its user agent or client hints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum Automation {
+    #[serde(rename = "headless chrome")]
+    HeadlessChrome,
+    #[serde(rename = "phantomjs")]
+    PhantomJs,
+    #[serde(rename = "puppeteer")]
+    Puppeteer,
+    #[serde(rename = "playwright")]
+    Playwright,
+    #[serde(rename = "selenium")]
+    Selenium,
+}
+
+impl Automation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Automation::HeadlessChrome => "headless chrome",
+            Automation::PhantomJs => "phantomjs",
+            Automation::Puppeteer => "puppeteer",
+            Automation::Playwright => "playwright",
+            Automation::Selenium => "selenium",
+        }
+    }
+}
+
+/// The automation driving the browser, if its user agent or hints give it away.
+pub(crate) fn lookup(ua: &str, client_hints: Option<&ClientHint>) -> Result<Option<Automation>> {
+    // the tools driving a browser before the browser, headless chrome is what
+    // puppeteer and playwright drive by default.
+    static PUPPETEER: Lazy<Regex> = static_user_agent_match!(r#"Puppeteer"#);
+    static PLAYWRIGHT: Lazy<Regex> = static_user_agent_match!(r#"Playwright"#);
+    static SELENIUM: Lazy<Regex> = static_user_agent_match!(r#"Selenium|WebDriver"#);
+    static HEADLESS_CHROME: Lazy<Regex> = static_user_agent_match!(r#"HeadlessChrome"#);
+    static PHANTOMJS: Lazy<Regex> = static_user_agent_match!(r#"PhantomJS"#);
+
+    for (regex, automation) in [
+        (&PUPPETEER, Automation::Puppeteer),
+        (&PLAYWRIGHT, Automation::Playwright),
+        (&SELENIUM, Automation::Selenium),
+        (&HEADLESS_CHROME, Automation::HeadlessChrome),
+        (&PHANTOMJS, Automation::PhantomJs),
+    ] {
+        if regex.is_match(ua)? {
+            return Ok(Some(automation));
+        }
+    }
+
+    // headless chrome with a user agent of its own still names itself in the brands.
+    let headless_brand = client_hints.is_some_and(|client_hints| {
+        client_hints
+            .full_version_list
+            .iter()
+            .any(|(brand, _)| brand == "HeadlessChrome")
+    });
+
+    Ok(headless_brand.then_some(Automation::HeadlessChrome))
+}
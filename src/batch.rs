@@ -0,0 +1,126 @@
+//! Parsing many user agents at once, such as a batch of log lines.
+
+use crate::error::Result;
+
+use std::collections::HashMap;
+
+use crate::device_detector::{Detection, DeviceDetector};
+
+// how many inputs `parse_iter` takes from its iterator at a time, unless told otherwise.
+const DEFAULT_BATCH_SIZE: usize = 1024;
+
+/// Request headers as passed to [`DeviceDetector::parse`].
+pub type Headers = Vec<(String, String)>;
+
+impl DeviceDetector {
+    /// Parses every input, returning the results in input order. Identical inputs,
+    /// same user agent and same headers, are only parsed once per batch. With the
+    /// `parallel` feature the distinct inputs are spread over rayon's worker pool.
+    pub fn parse_batch<S: AsRef<str>>(
+        &self,
+        inputs: &[(S, Option<Headers>)],
+    ) -> Vec<Result<Detection>> {
+        let mut unique: Vec<(&str, Option<&Headers>)> = Vec::new();
+        let mut seen = HashMap::new();
+        let slots: Vec<usize> = inputs
+            .iter()
+            .map(|(ua, headers)| {
+                let input = (ua.as_ref(), headers.as_ref());
+                *seen.entry(input).or_insert_with(|| {
+                    unique.push(input);
+                    unique.len() - 1
+                })
+            })
+            .collect();
+
+        let parse = |&(ua, headers): &(&str, Option<&Headers>)| self.parse(ua, headers.cloned());
+
+        #[cfg(feature = "parallel")]
+        let results: Vec<Result<Detection>> = {
+            use rayon::prelude::*;
+            unique.par_iter().map(parse).collect()
+        };
+
+        #[cfg(not(feature = "parallel"))]
+        let results: Vec<Result<Detection>> = unique.iter().map(parse).collect();
+
+        // repeated inputs get copies, the last of them the result itself.
+        let mut remaining = vec![0usize; results.len()];
+        for &slot in slots.iter() {
+            remaining[slot] += 1;
+        }
+
+        let mut results: Vec<Option<Result<Detection>>> = results.into_iter().map(Some).collect();
+        slots
+            .into_iter()
+            .map(|slot| {
+                remaining[slot] -= 1;
+                if remaining[slot] == 0 {
+                    results[slot].take().expect("result handed out once")
+                } else {
+                    results[slot].clone().expect("result handed out once")
+                }
+            })
+            .collect()
+    }
+
+    /// Parses a stream of inputs lazily, a batch at a time with
+    /// [`parse_batch`](Self::parse_batch), yielding results in input order.
+    pub fn parse_iter<I, S>(&self, inputs: I) -> ParseIter<'_, I::IntoIter, S>
+    where
+        I: IntoIterator<Item = (S, Option<Headers>)>,
+        S: AsRef<str>,
+    {
+        ParseIter {
+            detector: self,
+            inputs: inputs.into_iter(),
+            batch_size: DEFAULT_BATCH_SIZE,
+            batch: Vec::new(),
+            results: Vec::new().into_iter(),
+        }
+    }
+}
+
+/// Results of [`DeviceDetector::parse_iter`].
+pub struct ParseIter<'d, I, S> {
+    detector: &'d DeviceDetector,
+    inputs: I,
+    batch_size: usize,
+    batch: Vec<(S, Option<Headers>)>,
+    results: std::vec::IntoIter<Result<Detection>>,
+}
+
+impl<I, S> ParseIter<'_, I, S> {
+    /// How many inputs to parse at once, 1024 by default. Larger batches dedupe and
+    /// parallelize better, at the cost of holding on to more inputs and results.
+    pub fn batch_size(self, batch_size: usize) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            ..self
+        }
+    }
+}
+
+impl<I, S> Iterator for ParseIter<'_, I, S>
+where
+    I: Iterator<Item = (S, Option<Headers>)>,
+    S: AsRef<str>,
+{
+    type Item = Result<Detection>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(res) = self.results.next() {
+            return Some(res);
+        }
+
+        self.batch.clear();
+        self.batch
+            .extend(self.inputs.by_ref().take(self.batch_size));
+        if self.batch.is_empty() {
+            return None;
+        }
+
+        self.results = self.detector.parse_batch(&self.batch).into_iter();
+        self.results.next()
+    }
+}
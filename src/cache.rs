@@ -0,0 +1,319 @@
+//! Caching of detections. A [`DeviceDetector`](crate::device_detector::DeviceDetector)
+//! caches nothing unless given a [`DetectionCache`] through its builder, either one of
+//! the implementations here or a cache layer of your own.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::device_detector::Detection;
+
+/// Counts of a cache since it was created.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries dropped to make room for others.
+    pub evictions: u64,
+}
+
+/// Somewhere to keep detections. Keys are built from the user agent along with
+/// any client hints, and are opaque otherwise.
+pub trait DetectionCache: Send + Sync {
+    fn get(&self, key: &str) -> Option<Detection>;
+
+    fn insert(&self, key: String, detection: Detection);
+
+    /// Drops every entry, eg. to free those made with a rule database no longer in
+    /// use, which are otherwise left to be evicted. Does nothing by default.
+    fn invalidate_all(&self) {}
+
+    /// Counts since this cache was created, all zero for caches not keeping any.
+    fn stats(&self) -> CacheStats {
+        CacheStats::default()
+    }
+}
+
+impl<T: DetectionCache + ?Sized> DetectionCache for Arc<T> {
+    fn get(&self, key: &str) -> Option<Detection> {
+        (**self).get(key)
+    }
+
+    fn insert(&self, key: String, detection: Detection) {
+        (**self).insert(key, detection)
+    }
+
+    fn invalidate_all(&self) {
+        (**self).invalidate_all()
+    }
+
+    fn stats(&self) -> CacheStats {
+        (**self).stats()
+    }
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl Counters {
+    fn lookup(&self, res: Option<Detection>) -> Option<Detection> {
+        let counter = if res.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        res
+    }
+
+    #[cfg(feature = "cache")]
+    fn evicted(&self) {
+        self.evictions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Caches nothing, every lookup is a miss.
+#[derive(Debug, Default)]
+pub struct NoCache {
+    counters: Counters,
+}
+
+impl NoCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl DetectionCache for NoCache {
+    fn get(&self, _key: &str) -> Option<Detection> {
+        self.counters.lookup(None)
+    }
+
+    fn insert(&self, _key: String, _detection: Detection) {}
+
+    fn stats(&self) -> CacheStats {
+        self.counters.stats()
+    }
+}
+
+#[cfg(feature = "cache")]
+pub use self::moka_cache::MokaCache;
+#[cfg(feature = "cache")]
+pub use self::thread_local_cache::ThreadLocalLruCache;
+
+#[cfg(feature = "cache")]
+mod moka_cache {
+    use moka::notification::RemovalCause;
+    use moka::sync::Cache;
+
+    use std::sync::Arc;
+
+    use super::{CacheStats, Counters, DetectionCache};
+    use crate::device_detector::Detection;
+
+    #[derive(Debug)]
+    enum Capacity {
+        Entries(u64),
+        Bytes(u64),
+    }
+
+    /// A cache shared by every thread, bounded either by entries or by an estimate
+    /// of the memory its keys and detections take up.
+    pub struct MokaCache {
+        cache: Cache<String, Detection>,
+        counters: Arc<Counters>,
+    }
+
+    impl MokaCache {
+        /// Holds up to `entries` detections.
+        pub fn with_max_entries(entries: u64) -> Self {
+            Self::new(Capacity::Entries(entries))
+        }
+
+        /// Holds up to about `bytes` worth of keys and detections. Each entry is
+        /// weighed as its key, the size of a detection and the strings it owns,
+        /// leaving out the little it takes to allocate them.
+        pub fn with_max_bytes(bytes: u64) -> Self {
+            Self::new(Capacity::Bytes(bytes))
+        }
+
+        fn new(capacity: Capacity) -> Self {
+            let counters = Arc::new(Counters::default());
+
+            let evictions = counters.clone();
+            let builder = Cache::builder().eviction_listener(move |_key, _value, cause| {
+                if cause == RemovalCause::Size {
+                    evictions.evicted();
+                }
+            });
+
+            let cache = match capacity {
+                Capacity::Entries(entries) => builder.max_capacity(entries).build(),
+                Capacity::Bytes(bytes) => builder
+                    .max_capacity(bytes)
+                    .weigher(|key: &String, detection: &Detection| {
+                        let weight =
+                            key.len() + std::mem::size_of::<Detection>() + owned(detection);
+                        weight.try_into().unwrap_or(u32::MAX)
+                    })
+                    .build(),
+            };
+
+            Self { cache, counters }
+        }
+    }
+
+    /// The length of the strings a detection owns.
+    fn owned(detection: &Detection) -> usize {
+        fn len(field: &Option<String>) -> usize {
+            field.as_ref().map_or(0, String::len)
+        }
+
+        match detection {
+            Detection::Known(known) => {
+                let client = known.client.as_ref().map_or(0, |client| {
+                    client.name.len()
+                        + len(&client.version)
+                        + len(&client.engine)
+                        + len(&client.engine_version)
+                        + len(&client.short_name)
+                        + len(&client.url)
+                        + len(&client.subtype)
+                        + len(&client.feed_id)
+                });
+                let device = known
+                    .device
+                    .as_ref()
+                    .map_or(0, |device| len(&device.brand) + len(&device.model));
+                let os = known.os.as_ref().map_or(0, |os| {
+                    os.name.len()
+                        + len(&os.version)
+                        + len(&os.platform)
+                        + len(&os.family)
+                        + len(&os.short_name)
+                });
+                client + device + os
+            }
+            Detection::Bot(bot) => {
+                bot.name.len()
+                    + len(&bot.url)
+                    + len(&bot.feed_id)
+                    + bot
+                        .producer
+                        .as_ref()
+                        .map_or(0, |producer| len(&producer.name) + len(&producer.url))
+            }
+            Detection::DiscardedBot => 0,
+        }
+    }
+
+    impl DetectionCache for MokaCache {
+        fn get(&self, key: &str) -> Option<Detection> {
+            self.counters.lookup(self.cache.get(key))
+        }
+
+        fn insert(&self, key: String, detection: Detection) {
+            self.cache.insert(key, detection)
+        }
+
+        fn invalidate_all(&self) {
+            self.cache.invalidate_all()
+        }
+
+        fn stats(&self) -> CacheStats {
+            self.counters.stats()
+        }
+    }
+}
+
+#[cfg(feature = "cache")]
+mod thread_local_cache {
+    use lru::LruCache;
+    use thread_local::ThreadLocal;
+
+    use std::cell::{RefCell, RefMut};
+    use std::num::NonZeroUsize;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::{CacheStats, Counters, DetectionCache};
+    use crate::device_detector::Detection;
+
+    /// A separate lru cache for each thread, so that threads never wait on one
+    /// another, at the cost of each thread only benefiting from its own detections.
+    pub struct ThreadLocalLruCache {
+        entries: NonZeroUsize,
+        caches: ThreadLocal<RefCell<ThreadCache>>,
+        // bumped to invalidate every thread's cache, each clears its own on next use
+        // as only the owning thread may borrow it.
+        generation: AtomicU64,
+        counters: Counters,
+    }
+
+    struct ThreadCache {
+        generation: u64,
+        entries: LruCache<String, Detection>,
+    }
+
+    impl ThreadLocalLruCache {
+        /// Holds up to `entries` detections per thread, at least one.
+        pub fn new(entries: usize) -> Self {
+            Self {
+                entries: NonZeroUsize::new(entries).unwrap_or(NonZeroUsize::MIN),
+                caches: ThreadLocal::new(),
+                generation: AtomicU64::new(0),
+                counters: Counters::default(),
+            }
+        }
+
+        fn cache(&self) -> RefMut<'_, LruCache<String, Detection>> {
+            let generation = self.generation.load(Ordering::Acquire);
+            let cache = self.caches.get_or(|| {
+                RefCell::new(ThreadCache {
+                    generation,
+                    entries: LruCache::new(self.entries),
+                })
+            });
+
+            let mut cache = cache.borrow_mut();
+            if cache.generation != generation {
+                cache.entries.clear();
+                cache.generation = generation;
+            }
+            RefMut::map(cache, |cache| &mut cache.entries)
+        }
+    }
+
+    impl DetectionCache for ThreadLocalLruCache {
+        fn get(&self, key: &str) -> Option<Detection> {
+            let res = self.cache().get(key).cloned();
+            self.counters.lookup(res)
+        }
+
+        fn insert(&self, key: String, detection: Detection) {
+            let mut cache = self.cache();
+            let replaced = cache.contains(&key);
+            if cache.push(key, detection).is_some() && !replaced {
+                self.counters.evicted();
+            }
+        }
+
+        fn invalidate_all(&self) {
+            self.generation.fetch_add(1, Ordering::Release);
+        }
+
+        fn stats(&self) -> CacheStats {
+            self.counters.stats()
+        }
+    }
+}
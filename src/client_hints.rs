@@ -1,4 +1,4 @@
-use anyhow::Result;
+use crate::error::{DetectError, Result};
 
 use once_cell::sync::Lazy;
 
@@ -51,7 +51,19 @@ pub struct ClientHint {
 }
 
 impl ClientHint {
+    /// Reads the client hints out of request headers. Hints that don't follow their
+    /// syntax are read as best we can, or ignored, like upstream does.
     pub fn from_headers(headers: Vec<(String, String)>) -> Result<ClientHint> {
+        Self::parse_headers(headers, false)
+    }
+
+    /// Like [`Self::from_headers`], but fails with [`DetectError::InvalidClientHint`] on
+    /// a brand list, mobile flag or form factor list that doesn't follow its syntax.
+    pub fn from_headers_strict(headers: Vec<(String, String)>) -> Result<ClientHint> {
+        Self::parse_headers(headers, true)
+    }
+
+    fn parse_headers(headers: Vec<(String, String)>, strict: bool) -> Result<ClientHint> {
         let mut architecture = None;
         let mut bitness = None;
         let mut mobile = false;
@@ -70,6 +82,9 @@ impl ClientHint {
         // println!("headers: {:?}", headers);
         for (header, value) in headers {
             let header = header.replace('_', "-").to_lowercase();
+            if strict {
+                check_syntax(header.trim(), &value)?;
+            }
             match header.trim() {
                 "sec-ch-ua-arch" => {
                     architecture = Some(value.trim_matches('"').to_owned());
@@ -83,9 +98,7 @@ impl ClientHint {
                     // the php version interspersed actual headers and mock variable
                     // names in its code, so I don't know which of these values actually
                     // could come up in real user agents.
-                    if value == "1" || value == "true" || value == "yes" || value == "?1" {
-                        mobile = true;
-                    }
+                    mobile |= matches!(value.as_str(), "1" | "true" | "yes" | "?1");
                 }
 
                 "sec-ch-ua-model" => {
@@ -107,25 +120,20 @@ impl ClientHint {
                     }
                 }
 
-                "x-requested-with" | "http-x-requested-with" => {
-                    if value != "xmlhttprequest" {
-                        app = Some(value.to_owned());
-                    }
+                "x-requested-with" | "http-x-requested-with" if value != "xmlhttprequest" => {
+                    app = Some(value.to_owned());
                 }
 
                 "sec-ch-ua-full-version" => {
                     ua_full_version = Some(value.trim_matches('"').to_owned());
                 }
 
-                "sec-ch-ua" => {
-                    if full_version_list.is_empty() {
-                        for x in BRAND_REGEX.captures_iter(&value) {
-                            let res = x?;
-                            let brand = res.get(1).map(|x| x.as_str()).unwrap_or_else(|| "");
-                            let brand_version =
-                                res.get(2).map(|x| x.as_str()).unwrap_or_else(|| "");
-                            full_version_list.push((brand.to_owned(), brand_version.to_owned()));
-                        }
+                "sec-ch-ua" if full_version_list.is_empty() => {
+                    for x in BRAND_REGEX.captures_iter(&value) {
+                        let res = x?;
+                        let brand = res.get(1).map(|x| x.as_str()).unwrap_or_else(|| "");
+                        let brand_version = res.get(2).map(|x| x.as_str()).unwrap_or_else(|| "");
+                        full_version_list.push((brand.to_owned(), brand_version.to_owned()));
                     }
                 }
 
@@ -174,4 +182,56 @@ impl ClientHint {
 
         Ok(res)
     }
+
+    /// Every field detection depends on, in a canonical form, so that the same hints
+    /// compare equal no matter the order or spelling of the headers they came from,
+    /// while any difference that could change a detection shows.
+    pub(crate) fn canonical(&self) -> serde_json::Value {
+        // only membership of a form factor matters, not its position.
+        let mut form_factors = self.form_factors.clone();
+        form_factors.sort();
+        form_factors.dedup();
+
+        // fields in alphabetical order. The order of full_version_list does matter.
+        serde_json::json!([
+            self.app,
+            self.architecture,
+            self.bitness,
+            form_factors,
+            self.full_version_list,
+            self.mobile,
+            self.model,
+            self.platform,
+            self.platform_version,
+            self.ua_full_version,
+        ])
+    }
+}
+
+// Structured header syntax of the hints that are lists or booleans, the rest are
+// plain strings, quoted or not.
+fn check_syntax(header: &str, value: &str) -> Result<()> {
+    static BRAND_LIST: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r#"^\s*"[^"]*"\s*;\s*v="[^"]*"(\s*,\s*"[^"]*"\s*;\s*v="[^"]*")*\s*$"#).unwrap()
+    });
+    static STRING_LIST: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r#"^\s*"[^"]*"(\s*,\s*"[^"]*")*\s*$"#).unwrap());
+
+    let valid = match header {
+        // an empty list is a valid list.
+        _ if value.trim().is_empty() => true,
+        "sec-ch-ua" | "sec-ch-ua-full-version-list" => BRAND_LIST.is_match(value)?,
+        "sec-ch-ua-mobile" => matches!(value.trim(), "?0" | "?1"),
+        "sec-ch-ua-form-factors" => STRING_LIST.is_match(value)?,
+        _ => true,
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(DetectError::InvalidClientHint {
+            header: header.to_owned(),
+            value: value.to_owned(),
+        })
+    }
 }
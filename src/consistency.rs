@@ -0,0 +1,223 @@
+//! Checking the user agent against the client hints sent with it, which detection
+//! otherwise quietly prefers one or the other of, see
+//! [`DeviceDetectorBuilder::check_consistency`].
+//!
+//! A spoofed user agent sent by a browser that still sends honest client hints is
+//! given away by the two disagreeing.
+//!
+//! [`DeviceDetectorBuilder::check_consistency`]: crate::device_detector::DeviceDetectorBuilder::check_consistency
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use crate::accuracy;
+use crate::client_hints::ClientHint;
+use crate::error::{DetectError, Result};
+use crate::parsers::client::browsers;
+use crate::parsers::device::mobiles;
+use crate::parsers::device::{Device, DeviceType};
+use crate::parsers::oss::{self, OS};
+use crate::parsers::utils::SafeRegex as Regex;
+use crate::rule_database::RuleDatabase;
+use crate::trace::{self, Part};
+
+/// A way in which the user agent and its client hints disagree.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Inconsistency {
+    /// `Sec-CH-UA-Platform` names another os than the user agent.
+    Platform { hint: String, ua: String },
+
+    /// None of the `Sec-CH-UA` brands is the browser of the user agent, or of its
+    /// family.
+    Brand { hints: Vec<String>, ua: String },
+
+    /// `Sec-CH-UA-Mobile` disagrees with the type of device detected.
+    Mobile { hint: bool, device_type: DeviceType },
+
+    /// `Sec-CH-UA-Model` is another device than the one the user agent names.
+    Model { hint: String, ua: String },
+
+    /// The client or os version of the hints can't go with that of the user agent,
+    /// eg. Chromium 120 hints sent with a `Chrome/99` user agent.
+    Version {
+        part: Part,
+        hint: String,
+        ua: String,
+    },
+}
+
+/// Every inconsistency between the user agent and its hints, given the device
+/// detected from both.
+pub(crate) fn check(
+    db: &RuleDatabase,
+    ua: &str,
+    client_hints: &ClientHint,
+    device: Option<&Device>,
+) -> Result<Vec<Inconsistency>> {
+    // what the user agent alone says, kept out of any trace being recorded as it
+    // isn't part of the detection.
+    let (os_from_ua, client_from_ua, device_from_ua) = trace::untraced(|| {
+        Ok::<_, DetectError>((
+            db.oss.lookup(ua)?,
+            db.browsers.lookup(db, ua)?,
+            mobiles::lookup(db, ua)?,
+        ))
+    })?;
+
+    let os_from_hints = oss::os_from_hints(client_hints);
+    let brands: Vec<&str> = client_hints
+        .full_version_list
+        .iter()
+        .map(|(brand, _)| brand.as_str())
+        // GREASE brands, eg. `Not/A)Brand`, are there to be ignored.
+        .filter(|brand| !brand.contains("Brand"))
+        .collect();
+
+    let mut res = Vec::new();
+
+    if let (Some(hint), Some(ua_os)) = (&os_from_hints, &os_from_ua) {
+        if !same_os(hint, ua_os) {
+            res.push(Inconsistency::Platform {
+                hint: hint.name.clone(),
+                ua: ua_os.name.clone(),
+            });
+        } else if let Some((hint, ua)) = os_versions(ua, hint, ua_os) {
+            res.push(Inconsistency::Version {
+                part: Part::Os,
+                hint,
+                ua,
+            });
+        }
+    }
+
+    if let Some(client) = &client_from_ua {
+        let hinted: Vec<_> = brands
+            .iter()
+            .filter_map(|brand| browsers::browser_from_hint(brand))
+            .collect();
+
+        let same = hinted.iter().any(|browser| {
+            browser.name == client.name
+                || (browser.family.is_some() && browser.family.as_deref() == client.family())
+        });
+
+        if !hinted.is_empty() && !same {
+            res.push(Inconsistency::Brand {
+                hints: brands.iter().map(|brand| (*brand).to_owned()).collect(),
+                ua: client.name.clone(),
+            });
+        }
+    }
+
+    // without a brand list the mobile flag was likely never sent, it is then false.
+    if let Some(device_type) = device.and_then(|device| device.device_type.as_ref()) {
+        use DeviceType::*;
+
+        let mismatch = if client_hints.mobile {
+            matches!(device_type, Desktop | Notebook | Television | Console)
+        } else {
+            !brands.is_empty() && matches!(device_type, SmartPhone | FeaturePhone | Phablet)
+        };
+
+        if mismatch {
+            res.push(Inconsistency::Mobile {
+                hint: client_hints.mobile,
+                device_type: device_type.clone(),
+            });
+        }
+    }
+
+    if let Some(hint) = client_hints.model.as_deref().filter(|m| !m.is_empty()) {
+        if !ua.contains(hint) {
+            let from_hint = trace::untraced(|| mobiles::lookup(db, &format!("Android; {}", hint)))?;
+
+            if let (Some(from_hint), Some(from_ua)) = (from_hint, &device_from_ua) {
+                if from_ua.model.is_some()
+                    && (from_hint.brand != from_ua.brand || from_hint.model != from_ua.model)
+                {
+                    res.push(Inconsistency::Model {
+                        hint: hint.to_owned(),
+                        ua: [from_ua.brand.as_deref(), from_ua.model.as_deref()]
+                            .into_iter()
+                            .flatten()
+                            .collect::<Vec<_>>()
+                            .join(" "),
+                    });
+                }
+            }
+        }
+    }
+
+    if let Some((hint, ua)) = chromium_versions(ua, client_hints)? {
+        res.push(Inconsistency::Version {
+            part: Part::Client,
+            hint,
+            ua,
+        });
+    }
+
+    Ok(res)
+}
+
+fn same_os(hint: &OS, ua: &OS) -> bool {
+    let family = |os: &OS| os.family.clone().unwrap_or_else(|| os.name.clone());
+
+    // hints that detection itself takes to be the os of the user agent.
+    let allowed = [
+        ("GNU/Linux", "Chrome OS"),
+        ("Android", "Chrome OS"),
+        ("GNU/Linux", "Meta Horizon"),
+    ];
+
+    family(hint) == family(ua)
+        || allowed
+            .iter()
+            .any(|(h, u)| hint.name == *h && ua.name == *u)
+}
+
+// os versions of the same os that can't both be true, skipping those a reduced user
+// agent freezes.
+fn os_versions(ua: &str, hint: &OS, ua_os: &OS) -> Option<(String, String)> {
+    let hint_version = hint.version.as_deref()?;
+    let ua_version = ua_os.version.as_deref()?;
+    let major = |version: &str| version.split('.').next().unwrap_or("").to_owned();
+
+    let frozen = match hint.name.as_str() {
+        // Windows 11 still says `NT 10.0`.
+        "Windows" => hint_version == "11" && ua_version == "10",
+        "Mac" => ua_version.starts_with("10.15"),
+        // unlike `Android 10; K`, `Android 13)` still has the major version.
+        "Android" => accuracy::reduced_android(ua)
+            .unwrap_or(None)
+            .is_some_and(|reduced| reduced.is_android_10_k()),
+        _ => false,
+    };
+
+    // hints of old windows versions aren't mapped to a version.
+    let unmapped = hint.name == "Windows" && hint_version.starts_with("0.");
+
+    (!frozen && !unmapped && major(hint_version) != major(ua_version))
+        .then(|| (hint_version.to_owned(), ua_version.to_owned()))
+}
+
+// the major version of the Chromium brand against that of `Chrome/` in the user agent.
+fn chromium_versions(ua: &str, client_hints: &ClientHint) -> Result<Option<(String, String)>> {
+    static CHROME_VERSION: Lazy<Regex> = Lazy::new(|| Regex::new(r"Chrome/(\d+)").unwrap());
+
+    let hint = client_hints
+        .full_version_list
+        .iter()
+        .find(|(brand, _)| brand == "Chromium")
+        .map(|(_, version)| version.as_str());
+
+    let (hint, captures) = match (hint, CHROME_VERSION.captures(ua)?) {
+        (Some(hint), Some(captures)) => (hint, captures),
+        _ => return Ok(None),
+    };
+
+    let ua_major = &captures[1];
+    let hint_major = hint.split('.').next().unwrap_or("");
+
+    Ok((hint_major != ua_major).then(|| (hint.to_owned(), ua_major.to_owned())))
+}
@@ -1,14 +1,25 @@
-use anyhow::Result;
-
 use serde::Serialize;
 
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::accuracy::{self, VersionAccuracy};
+use crate::automation::{self, Automation};
+use crate::cache::{CacheStats, DetectionCache};
 use crate::client_hints::ClientHint;
+use crate::consistency::{self, Inconsistency};
+use crate::error::Result;
 use crate::parsers::client::ClientType;
 use crate::parsers::device::DeviceType;
 use crate::parsers::{bot, client, device, oss};
+use crate::pipeline::{self, Parser, Pipeline, Position, PostProcessor, Stage};
+use crate::rule_database::RuleDatabase;
+use crate::trace::{self, Explanation, Provenance, Reason};
+use crate::verify::{BotVerifier, Verification};
 
 #[cfg(feature = "cache")]
-use moka::sync::Cache;
+use crate::cache::MokaCache;
 
 pub use bot::Bot;
 
@@ -18,6 +29,9 @@ pub use bot::Bot;
 pub enum Detection {
     Known(KnownDevice),
     Bot(Bot),
+    /// A bot, whose details were discarded, see
+    /// [`DeviceDetectorBuilder::discard_bot_information`].
+    DiscardedBot,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -25,6 +39,26 @@ pub struct KnownDevice {
     pub client: Option<client::Client>,
     pub device: Option<device::Device>,
     pub os: Option<oss::OS>,
+    /// What drives the browser, for browsers driven by automation rather than by a
+    /// person. These are told apart from bots, which say who they are.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub automation: Option<Automation>,
+    /// Whether the user agent was reduced, or otherwise frozen at some versions, eg.
+    /// `Android 10; K` or Chrome's `120.0.0.0`. Only serialized when it was.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub reduced_ua: bool,
+    /// Which versions are exact, and which frozen by a reduced user agent. Only
+    /// serialized when some version isn't exact.
+    #[serde(skip_serializing_if = "VersionAccuracy::is_exact")]
+    pub version_accuracy: VersionAccuracy,
+    /// How the user agent and its client hints disagree, when checked, see
+    /// [`DeviceDetectorBuilder::check_consistency`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consistency: Option<Vec<Inconsistency>>,
+    /// Where each field came from, when recorded, see
+    /// [`DeviceDetectorBuilder::record_provenance`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
 }
 
 impl Detection {
@@ -42,9 +76,23 @@ impl Detection {
         }
     }
 
+    /// How the user agent and its client hints disagree, `None` for bots and unless
+    /// checked, see [`DeviceDetectorBuilder::check_consistency`].
+    pub fn consistency(&self) -> Option<&[Inconsistency]> {
+        self.get_known_device()
+            .and_then(|known| known.consistency.as_deref())
+    }
+
+    /// Whether the user agent was reduced, see [`KnownDevice::reduced_ua`], never for
+    /// bots.
+    pub fn reduced_ua(&self) -> bool {
+        self.get_known_device()
+            .is_some_and(|known| known.reduced_ua)
+    }
+
     /// Did we detect a bot? If not, then it is a known device.
     pub fn is_bot(&self) -> bool {
-        matches!(self, Self::Bot(_))
+        matches!(self, Self::Bot(_) | Self::DiscardedBot)
     }
     /// This is purely to aid in generating test cases, you should not rely on this for
     /// actual production usage. Only useful for normal stuff, not bots, etc.
@@ -53,8 +101,7 @@ impl Detection {
             Self::Known(known) => known
                 .client
                 .as_ref()
-                .and_then(|x| x.browser.as_ref())
-                .and_then(|x| x.family.as_deref())
+                .and_then(|x| x.family())
                 .unwrap_or("Unknown")
                 .to_owned(),
             _ => "Unknown".to_owned(),
@@ -240,6 +287,7 @@ impl Detection {
                     "bot": serde_json::to_value(bot).unwrap()
                 })
             }
+            Detection::DiscardedBot => serde_json::json!({ "bot": true }),
         }
     }
 }
@@ -301,6 +349,11 @@ impl KnownDevice {
         false
     }
 
+    /// Whether the browser is driven by automation, see [`KnownDevice::automation`].
+    pub fn is_automated(&self) -> bool {
+        self.automation.is_some()
+    }
+
     pub fn is_pim(&self) -> bool {
         self.client
             .as_ref()
@@ -539,45 +592,301 @@ impl KnownDevice {
 
 // use std::alloc::System;
 
-#[cfg(feature = "cache")]
-type DetectionCache = Cache<String, Detection>;
+/// The client and device parsers, which can be turned off individually, see
+/// [`DeviceDetectorBuilder::disable_parser`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParserFamily {
+    FeedReader,
+    MobileApp,
+    MediaPlayer,
+    Pim,
+    Library,
+    Browser,
+
+    /// HbbTV televisions.
+    Television,
+    ShellTv,
+    Notebook,
+    Console,
+    CarBrowser,
+    Camera,
+    PortableMediaPlayer,
+    Mobile,
+}
+
+/// How much of client, engine and os versions to keep, eg. `114.0.5735.199` becomes
+/// `114`, `114.0` or `114.0.5735`. Versions are kept in full by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VersionTruncation {
+    Major,
+    Minor,
+    Patch,
+    #[default]
+    None,
+}
+
+impl VersionTruncation {
+    fn truncate(self, version: &mut Option<String>) {
+        let parts = match self {
+            Self::Major => 1,
+            Self::Minor => 2,
+            Self::Patch => 3,
+            Self::None => return,
+        };
+
+        if let Some(v) = version {
+            if let Some((end, _)) = v.match_indices('.').nth(parts - 1) {
+                v.truncate(end);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct DetectionOptions {
+    skip_bot_detection: bool,
+    discard_bot_information: bool,
+    version_truncation: VersionTruncation,
+    disabled_parsers: Vec<ParserFamily>,
+    strict_client_hints: bool,
+    check_consistency: bool,
+    record_provenance: bool,
+    pipeline: Pipeline,
+}
+
+impl DetectionOptions {
+    fn enabled(&self, parser: ParserFamily) -> bool {
+        !self.disabled_parsers.contains(&parser)
+    }
+}
 
 #[derive(Clone)]
 pub struct DeviceDetector {
-    #[cfg(feature = "cache")]
-    caching: bool,
-    #[cfg(feature = "cache")]
-    cache: DetectionCache,
+    database: Arc<RuleDatabase>,
+    cache: Option<Arc<dyn DetectionCache>>,
+    options: DetectionOptions,
+    verifiers: Vec<Arc<dyn BotVerifier>>,
+}
+
+/// Sets up a [`DeviceDetector`], see [`DeviceDetector::builder`].
+#[derive(Clone, Default)]
+pub struct DeviceDetectorBuilder {
+    database: Option<Arc<RuleDatabase>>,
+    cache: Option<Arc<dyn DetectionCache>>,
+    options: DetectionOptions,
+    verifiers: Vec<Arc<dyn BotVerifier>>,
+}
+
+impl DeviceDetectorBuilder {
+    /// The rules to detect with, those compiled into this binary by default.
+    pub fn database(self, database: Arc<RuleDatabase>) -> Self {
+        Self {
+            database: Some(database),
+            ..self
+        }
+    }
+
+    /// Where to cache detections, nothing is cached by default. Pass an `Arc` to
+    /// keep a handle on the cache, eg. to share it with other detectors set up the
+    /// same way, as detections depend on the options below.
+    pub fn cache(self, cache: impl DetectionCache + 'static) -> Self {
+        Self {
+            cache: Some(Arc::new(cache)),
+            ..self
+        }
+    }
+
+    /// Don't look for bots at all, bots are then detected like any other client.
+    pub fn skip_bot_detection(mut self, skip: bool) -> Self {
+        self.options.skip_bot_detection = skip;
+        self
+    }
+
+    /// Only tell bots apart, as [`Detection::DiscardedBot`], without the details of
+    /// which bot it was.
+    pub fn discard_bot_information(mut self, discard: bool) -> Self {
+        self.options.discard_bot_information = discard;
+        self
+    }
+
+    /// Truncates client, engine and os versions. This happens once detection is done,
+    /// so it never changes what gets detected.
+    pub fn version_truncation(mut self, truncation: VersionTruncation) -> Self {
+        self.options.version_truncation = truncation;
+        self
+    }
+
+    /// Skips a client or device parser, every parser runs by default.
+    pub fn disable_parser(mut self, parser: ParserFamily) -> Self {
+        if self.options.enabled(parser) {
+            self.options.disabled_parsers.push(parser);
+        }
+        self
+    }
+
+    /// Runs a parser turned off by [`Self::disable_parser`] again.
+    pub fn enable_parser(mut self, parser: ParserFamily) -> Self {
+        self.options.disabled_parsers.retain(|x| *x != parser);
+        self
+    }
+
+    /// Fails a detection with [`DetectError::InvalidClientHint`] on a client hint header
+    /// that doesn't follow its syntax. By default such headers are ignored, like
+    /// upstream does.
+    ///
+    /// [`DetectError::InvalidClientHint`]: crate::error::DetectError::InvalidClientHint
+    pub fn strict_client_hints(mut self, strict: bool) -> Self {
+        self.options.strict_client_hints = strict;
+        self
+    }
+
+    /// Reports how the user agent and its client hints disagree, eg. a Windows user
+    /// agent sent with Android hints, with every detection of a device, see
+    /// [`Detection::consistency`]. Detections without hints are consistent.
+    pub fn check_consistency(mut self, check: bool) -> Self {
+        self.options.check_consistency = check;
+        self
+    }
+
+    /// Records where each field of every detection of a device came from, the user
+    /// agent, client hints, a heuristic or a vendor fragment, see
+    /// [`KnownDevice::provenance`]. It costs recording the steps of detection, as
+    /// [`DeviceDetector::explain`] does.
+    pub fn record_provenance(mut self, record: bool) -> Self {
+        self.options.record_provenance = record;
+        self
+    }
+
+    /// Inserts a client parser of your own into the chain of client parsers, which
+    /// are tried in turn until one detects a client. Parsers inserted at the same
+    /// position run in the order they were inserted, unless inserted `First`. Fails
+    /// with [`DetectError::InvalidInput`] on a position relative to a device parser.
+    ///
+    /// [`DetectError::InvalidInput`]: crate::error::DetectError::InvalidInput
+    pub fn insert_client_parser(
+        mut self,
+        position: Position,
+        parser: impl Parser<Output = client::Client> + 'static,
+    ) -> Result<Self> {
+        let stage = Stage::Custom(Arc::new(parser));
+        pipeline::insert(&mut self.options.pipeline.clients, position, stage)?;
+        Ok(self)
+    }
+
+    /// Inserts a device parser of your own into the chain of device parsers, see
+    /// [`Self::insert_client_parser`]. Whatever it detects still goes through upstream's
+    /// heuristics, eg. for the device type.
+    pub fn insert_device_parser(
+        mut self,
+        position: Position,
+        parser: impl Parser<Output = device::Device> + 'static,
+    ) -> Result<Self> {
+        let stage = Stage::Custom(Arc::new(parser));
+        pipeline::insert(&mut self.options.pipeline.devices, position, stage)?;
+        Ok(self)
+    }
+
+    /// Rewrites every detection other than a bot once it is done, after any added
+    /// before.
+    pub fn post_process(mut self, processor: impl PostProcessor + 'static) -> Self {
+        self.options
+            .pipeline
+            .post_processors
+            .push(Arc::new(processor));
+        self
+    }
+
+    /// Checks bots detected by [`DeviceDetector::parse_with_ip`] against the client's
+    /// ip, after any verifier added before that couldn't tell.
+    pub fn verify_bots(mut self, verifier: impl BotVerifier + 'static) -> Self {
+        self.verifiers.push(Arc::new(verifier));
+        self
+    }
+
+    pub fn build(self) -> DeviceDetector {
+        DeviceDetector {
+            database: self.database.unwrap_or_else(RuleDatabase::embedded),
+            cache: self.cache,
+            options: self.options,
+            verifiers: self.verifiers,
+        }
+    }
 }
 
 impl DeviceDetector {
-    #[cfg(not(feature = "cache"))]
+    /// A detector using the rule database compiled into this binary.
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self {}
+        Self::with_database(RuleDatabase::embedded())
     }
 
-    #[cfg(feature = "cache")]
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
+    /// A detector using a rule database read at runtime from a directory laid out
+    /// like upstream's `regexes/` directory, eg. a newer checkout of matomo's rules.
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::with_database(Arc::new(RuleDatabase::from_dir(dir)?)))
+    }
+
+    /// A detector using a rule database built from in memory yaml, see
+    /// [`RuleDatabase::from_sources`].
+    pub fn from_sources<I, K, V>(sources: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        Ok(Self::with_database(Arc::new(RuleDatabase::from_sources(
+            sources,
+        )?)))
+    }
+
+    /// A detector using an already loaded rule database, which may be shared with
+    /// other detectors.
+    pub fn with_database(database: Arc<RuleDatabase>) -> Self {
+        Self::builder().database(database).build()
+    }
+
+    /// Sets up a detector with more than the defaults, eg. with a cache.
+    pub fn builder() -> DeviceDetectorBuilder {
+        DeviceDetectorBuilder::default()
+    }
+
+    /// The rule database this detector uses.
+    pub fn database(&self) -> &Arc<RuleDatabase> {
+        &self.database
+    }
+
+    /// A detector with the same settings and cache as this one, but using `database`.
+    /// Detections are cached per database, so neither detector is served the other's,
+    /// and those made with the old rules are left for the cache to evict.
+    pub fn rebuild_with_database(&self, database: Arc<RuleDatabase>) -> Self {
         Self {
-            caching: false,
-            cache: Cache::new(0),
+            database,
+            cache: self.cache.clone(),
+            options: self.options.clone(),
+            verifiers: self.verifiers.clone(),
         }
     }
 
+    /// Hits, misses and evictions of this detector's cache, if it has one.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(|cache| cache.stats())
+    }
+
     #[cfg(feature = "cache")]
     pub fn new_with_cache(entries: u64) -> Self {
+        Self::new().with_cache(entries)
+    }
+
+    /// Caches up to `entries` detections, keyed by user agent and client hints.
+    #[cfg(feature = "cache")]
+    pub fn with_cache(self, entries: u64) -> Self {
         Self {
-            caching: true,
-            cache: Cache::new(entries),
+            cache: Some(Arc::new(MokaCache::with_max_entries(entries))),
+            ..self
         }
     }
     pub fn parse(&self, ua: &str, headers: Option<Vec<(String, String)>>) -> Result<Detection> {
-        let client_hints = match headers {
-            Some(headers) => Some(ClientHint::from_headers(headers)?),
-            None => None,
-        };
+        let client_hints = self.client_hints(headers)?;
         self.parse_client_hints(ua, client_hints)
     }
 
@@ -586,40 +895,182 @@ impl DeviceDetector {
         ua: &str,
         client_hints: Option<ClientHint>,
     ) -> Result<Detection> {
-        let parse = || {
-            if let Some(bot) = bot::lookup_bot(ua)? {
-                return Ok(Detection::Bot(bot));
-            }
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return self.detect(ua, client_hints.as_ref()),
+        };
+
+        // the same user agent is detected differently depending on its hints,
+        // eg. reduced user agents, where only the hints carry the model. Detectors
+        // on different databases may share a cache, see `rebuild_with_database`.
+        let generation = self.database.generation;
+        let key = match &client_hints {
+            Some(client_hints) => serde_json::json!([generation, ua, client_hints.canonical()]),
+            None => serde_json::json!([generation, ua]),
+        }
+        .to_string();
 
-            let os = oss::lookup(ua, client_hints.as_ref())?;
+        if let Some(res) = cache.get(&key) {
+            return Ok(res);
+        };
 
-            let client = client::lookup(ua, client_hints.as_ref())?;
+        let known = self.detect(ua, client_hints.as_ref())?;
 
-            let device = device::lookup(ua, client.as_ref(), client_hints.as_ref(), os.as_ref())?;
+        cache.insert(key, known.clone());
 
-            let known = Detection::Known(KnownDevice { client, device, os });
+        Ok(known)
+    }
 
-            Ok::<_, anyhow::Error>(known)
+    /// Detects like [`Self::parse`], then verifies a bot against `ip`, the address of
+    /// the client that sent the user agent, see [`DeviceDetectorBuilder::verify_bots`].
+    pub fn parse_with_ip(
+        &self,
+        ua: &str,
+        headers: Option<Vec<(String, String)>>,
+        ip: IpAddr,
+    ) -> Result<Detection> {
+        let mut detection = self.parse(ua, headers)?;
+
+        // verification depends on the ip, so it is never cached.
+        if let Detection::Bot(bot) = &mut detection {
+            for verifier in &self.verifiers {
+                bot.verification = verifier.verify(bot, ua, ip)?;
+                if bot.verification != Verification::Unknown {
+                    break;
+                }
+            }
+        }
+
+        Ok(detection)
+    }
+
+    /// Detects like [`Self::parse`], along with a trace of which rule file and rule
+    /// produced each part of the detection, and which fields client hints or the
+    /// heuristics applied afterwards changed. The cache is bypassed.
+    pub fn explain(&self, ua: &str, headers: Option<Vec<(String, String)>>) -> Result<Explanation> {
+        let client_hints = self.client_hints(headers)?;
+        let (detection, trace) =
+            trace::explain(&self.database, || self.detect(ua, client_hints.as_ref()));
+
+        Ok(Explanation {
+            detection: detection?,
+            trace,
+        })
+    }
+
+    fn client_hints(&self, headers: Option<Vec<(String, String)>>) -> Result<Option<ClientHint>> {
+        let client_hints = match headers {
+            Some(headers) if self.options.strict_client_hints => {
+                Some(ClientHint::from_headers_strict(headers)?)
+            }
+            Some(headers) => Some(ClientHint::from_headers(headers)?),
+            None => None,
         };
+        Ok(client_hints)
+    }
 
-        #[cfg(feature = "cache")]
-        {
-            if !self.caching {
-                return Ok(parse()?);
+    fn detect(&self, ua: &str, client_hints: Option<&ClientHint>) -> Result<Detection> {
+        let detect = || {
+            let db = &*self.database;
+            let options = &self.options;
+            let enabled = |parser| options.enabled(parser);
+
+            if !options.skip_bot_detection {
+                if let Some(bot) = bot::lookup_bot(db, ua)? {
+                    if options.discard_bot_information {
+                        return Ok(Detection::DiscardedBot);
+                    }
+                    return Ok(Detection::Bot(bot));
+                }
+            }
+
+            let mut os = oss::lookup(db, ua, client_hints)?;
+
+            let pipeline = &options.pipeline;
+            let mut client = client::lookup(
+                db,
+                ua,
+                client_hints,
+                os.as_ref(),
+                &pipeline.clients,
+                enabled,
+            )?;
+
+            let device = device::lookup(
+                db,
+                ua,
+                client.as_ref(),
+                client_hints,
+                os.as_ref(),
+                &pipeline.devices,
+                enabled,
+            )?;
+
+            // before truncation, which would hide the frozen versions.
+            let reduced_ua = accuracy::is_reduced(ua)?;
+            let version_accuracy =
+                accuracy::version_accuracy(ua, client_hints, client.as_ref(), os.as_ref())?;
+
+            let truncation = options.version_truncation;
+            let before = trace::snapshot(client.as_ref());
+            if let Some(client) = &mut client {
+                truncation.truncate(&mut client.version);
+                truncation.truncate(&mut client.engine_version);
+            }
+            trace::changes(before, client.as_ref(), Reason::VersionTruncation);
+
+            let before = trace::snapshot(os.as_ref());
+            if let Some(os) = &mut os {
+                truncation.truncate(&mut os.version);
             }
+            trace::changes(before, os.as_ref(), Reason::VersionTruncation);
 
-            if let Some(res) = self.cache.get(ua) {
-                return Ok(res);
+            let consistency = match client_hints {
+                Some(client_hints) if options.check_consistency => {
+                    Some(consistency::check(db, ua, client_hints, device.as_ref())?)
+                }
+                _ if options.check_consistency => Some(Vec::new()),
+                _ => None,
             };
 
-            let known = parse()?;
+            let automation = automation::lookup(ua, client_hints)?;
+
+            let mut known = KnownDevice {
+                client,
+                device,
+                os,
+                automation,
+                reduced_ua,
+                version_accuracy,
+                consistency,
+                provenance: None,
+            };
 
-            self.cache.insert(ua.to_owned(), known.clone());
+            for processor in pipeline.post_processors.iter() {
+                let before = (
+                    trace::snapshot(known.client.as_ref()),
+                    trace::snapshot(known.device.as_ref()),
+                    trace::snapshot(known.os.as_ref()),
+                );
 
-            Ok(known)
-        }
+                processor.process(ua, client_hints, &mut known)?;
+
+                trace::changes(before.0, known.client.as_ref(), Reason::PostProcessor);
+                trace::changes(before.1, known.device.as_ref(), Reason::PostProcessor);
+                trace::changes(before.2, known.os.as_ref(), Reason::PostProcessor);
+            }
+
+            Ok(Detection::Known(known))
+        };
+
+        let res = if self.options.record_provenance {
+            trace::provenance(detect)
+        } else {
+            detect()
+        };
 
-        #[cfg(not(feature = "cache"))]
-        parse()
+        // a rule that failed to compile on first use, reported as it would be by
+        // RuleDatabase::validate.
+        res.map_err(|err| self.database.locate(err))
     }
 }
@@ -0,0 +1,144 @@
+//! Errors returned by the public api.
+
+use std::fmt;
+
+/// `Result` with [`DetectError`] as the default error.
+pub type Result<T, E = DetectError> = std::result::Result<T, E>;
+
+/// Why a detection, or loading a rule database, failed.
+///
+/// Every variant has a stable [`code`](DetectError::code) and [`name`](DetectError::name),
+/// which the ffi and http interfaces report as is. Codes are never reused, new kinds of
+/// errors get new codes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DetectError {
+    /// The input can't be used at all, eg. a user agent that isn't utf8.
+    InvalidInput(String),
+
+    /// A client hint header whose value doesn't follow the header's syntax. Only
+    /// reported with strict client hints, otherwise such headers are ignored like
+    /// upstream does.
+    InvalidClientHint { header: String, value: String },
+
+    /// A rule file that failed to load, or a rule of it that failed to compile.
+    RuleDatabase {
+        /// The rule file, relative to the `regexes/` directory. Empty for a regex that
+        /// isn't a rule's own, eg. one built from a rule's engine name.
+        file: String,
+        /// Index of the broken rule within that file, when a single rule is to blame.
+        rule: Option<usize>,
+        /// The regex that failed to compile, if that is what went wrong.
+        pattern: Option<String>,
+        message: String,
+    },
+
+    /// The backtracking regex engine gave up on a rule, eg. having hit its backtrack
+    /// limit on a pathological user agent.
+    RegexLimit { pattern: String, message: String },
+}
+
+impl DetectError {
+    /// The stable numeric code of this kind of error.
+    ///
+    /// | code | name                  |
+    /// |------|-----------------------|
+    /// | 1    | `invalid_input`       |
+    /// | 2    | `invalid_client_hint` |
+    /// | 3    | `rule_database`       |
+    /// | 4    | `regex_limit`         |
+    pub fn code(&self) -> u32 {
+        match self {
+            DetectError::InvalidInput(_) => 1,
+            DetectError::InvalidClientHint { .. } => 2,
+            DetectError::RuleDatabase { .. } => 3,
+            DetectError::RegexLimit { .. } => 4,
+        }
+    }
+
+    /// The stable name of this kind of error, see [`code`](Self::code).
+    pub fn name(&self) -> &'static str {
+        match self {
+            DetectError::InvalidInput(_) => "invalid_input",
+            DetectError::InvalidClientHint { .. } => "invalid_client_hint",
+            DetectError::RuleDatabase { .. } => "rule_database",
+            DetectError::RegexLimit { .. } => "regex_limit",
+        }
+    }
+
+    /// Whether the caller is to blame rather than the rules, eg. to pick between a
+    /// 400 and a 500 status.
+    pub fn is_input_error(&self) -> bool {
+        matches!(
+            self,
+            DetectError::InvalidInput(_) | DetectError::InvalidClientHint { .. }
+        )
+    }
+
+    pub(crate) fn regex_compile(pattern: &str, message: impl fmt::Display) -> Self {
+        DetectError::RuleDatabase {
+            file: String::new(),
+            rule: None,
+            pattern: Some(pattern.to_owned()),
+            message: message.to_string(),
+        }
+    }
+
+    // the file and rule of a regex that failed to compile.
+    pub(crate) fn in_rule(self, file: &str, rule: usize) -> Self {
+        match self {
+            DetectError::RuleDatabase {
+                pattern, message, ..
+            } => DetectError::RuleDatabase {
+                file: file.to_owned(),
+                rule: Some(rule),
+                pattern,
+                message,
+            },
+            err => err,
+        }
+    }
+
+    pub(crate) fn rule_database(file: &str, message: impl fmt::Display) -> Self {
+        DetectError::RuleDatabase {
+            file: file.to_owned(),
+            rule: None,
+            pattern: None,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for DetectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DetectError::InvalidInput(message) => write!(f, "invalid input: {}", message),
+            DetectError::InvalidClientHint { header, value } => {
+                write!(f, "invalid client hint {}: {:?}", header, value)
+            }
+            DetectError::RuleDatabase {
+                file,
+                rule,
+                pattern,
+                message,
+            } => {
+                if !file.is_empty() {
+                    write!(f, "{}", file)?;
+                    if let Some(rule) = rule {
+                        write!(f, " rule {}", rule)?;
+                    }
+                    write!(f, ": ")?;
+                }
+                if let Some(pattern) = pattern {
+                    write!(f, "compiling {}: ", pattern)?;
+                }
+                write!(f, "{}", message)
+            }
+            DetectError::RegexLimit { pattern, message } => {
+                write!(f, "regex gave up on {}: {}", pattern, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DetectError {}
@@ -1,10 +1,12 @@
 #![allow(clippy::missing_safety_doc)]
 
 use libc::c_char;
+use std::cell::RefCell;
 use std::ffi::{CStr, CString};
 use std::ptr::{null, null_mut};
 
 use crate::device_detector::{Detection, DeviceDetector};
+use crate::error::{DetectError, Result};
 use crate::parsers::bot::Bot;
 use crate::parsers::client::Client;
 use crate::parsers::device::Device;
@@ -24,10 +26,28 @@ impl RDDDeviceDetector {
             dd: DeviceDetector::new(),
         }
     }
+
+    #[allow(unused)]
+    pub fn from_dir(dir: &str, cache_size: u64) -> Result<RDDDeviceDetector> {
+        let dd = DeviceDetector::from_dir(dir)?;
+        #[cfg(feature = "cache")]
+        let dd = dd.with_cache(cache_size);
+        Ok(RDDDeviceDetector { dd })
+    }
 }
 
 pub struct RDDDetection {
     dt: Option<Detection>,
+    error: Option<DetectError>,
+}
+
+thread_local! {
+    // the error of the last call on this thread not returning a detection to hold it.
+    static LAST_ERROR: RefCell<Option<DetectError>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(error: Option<DetectError>) {
+    LAST_ERROR.with(|last| *last.borrow_mut() = error);
 }
 
 #[derive(Debug)]
@@ -59,6 +79,59 @@ pub extern "C" fn rdd_device_detector_new(cache_size: u64) -> *mut RDDDeviceDete
     Box::into_raw(Box::new(RDDDeviceDetector::new(cache_size)))
 }
 
+/// Loads the rule database from a directory laid out like upstream's `regexes/`
+/// directory. Returns null if the directory could not be loaded, see
+/// `rdd_last_error_code` and `rdd_last_error_message` for why.
+#[no_mangle]
+pub unsafe extern "C" fn rdd_device_detector_from_dir(
+    dir: *const c_char,
+    cache_size: u64,
+) -> *mut RDDDeviceDetector {
+    let dir = unsafe { CStr::from_ptr(dir) };
+
+    let res = match dir.to_str() {
+        Err(invalid_utf8) => Err(DetectError::InvalidInput(format!(
+            "directory is not utf8: {}",
+            invalid_utf8
+        ))),
+        Ok(dir) => RDDDeviceDetector::from_dir(dir, cache_size),
+    };
+
+    match res {
+        Ok(rdd) => {
+            set_last_error(None);
+            Box::into_raw(Box::new(rdd))
+        }
+        Err(err) => {
+            set_last_error(Some(err));
+            null_mut()
+        }
+    }
+}
+
+/// The stable code of the error the last `rdd_device_detector_from_dir` on this thread
+/// failed with, or 0 if it succeeded.
+#[no_mangle]
+pub extern "C" fn rdd_last_error_code() -> u32 {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map(|err| err.code()).unwrap_or(0))
+}
+
+/// The message of the error the last `rdd_device_detector_from_dir` on this thread failed
+/// with, or null if it succeeded.
+#[no_mangle]
+pub extern "C" fn rdd_last_error_message() -> *mut c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map(|err| {
+                CString::new(err.to_string().replace('\0', ""))
+                    .unwrap()
+                    .into_raw()
+            })
+            .unwrap_or(null_mut())
+    })
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rdd_lookup(
     rdd: *const RDDDeviceDetector,
@@ -67,28 +140,51 @@ pub unsafe extern "C" fn rdd_lookup(
     let rdd = unsafe { &*rdd };
     let ua = unsafe { CStr::from_ptr(ua) };
 
-    let rdd = match ua.to_str() {
-        Err(_invalid_utf8) => {
-            println!("invalid utf8");
-            RDDDetection { dt: None }
-        }
-        Ok(ua) => {
-            match rdd.dd.parse(ua, None) {
-                Ok(dd) => {
-                    // println!("dd: {:#?}", dd);
-                    RDDDetection { dt: Some(dd) }
-                }
-                Err(err) => {
-                    println!("error: {:?}", err);
-                    RDDDetection { dt: None }
-                }
-            }
-        }
+    let res = match ua.to_str() {
+        Err(invalid_utf8) => Err(DetectError::InvalidInput(format!(
+            "user agent is not utf8: {}",
+            invalid_utf8
+        ))),
+        Ok(ua) => rdd.dd.parse(ua, None),
+    };
+
+    let rdd = match res {
+        Ok(dd) => RDDDetection {
+            dt: Some(dd),
+            error: None,
+        },
+        Err(err) => RDDDetection {
+            dt: None,
+            error: Some(err),
+        },
     };
 
     Box::into_raw(Box::new(rdd))
 }
 
+/// The stable code of the error a lookup failed with, as given by `DetectError::code`,
+/// or 0 if it succeeded.
+#[no_mangle]
+pub unsafe extern "C" fn rdd_error_code(rdd: *const RDDDetection) -> u32 {
+    let rdd = unsafe { &*rdd };
+    rdd.error.as_ref().map(|err| err.code()).unwrap_or(0)
+}
+
+/// The message of the error a lookup failed with, or null if it succeeded.
+#[no_mangle]
+pub unsafe extern "C" fn rdd_error_message(rdd: *const RDDDetection) -> *mut c_char {
+    let rdd = unsafe { &*rdd };
+
+    rdd.error
+        .as_ref()
+        .map(|err| {
+            CString::new(err.to_string().replace('\0', ""))
+                .unwrap()
+                .into_raw()
+        })
+        .unwrap_or(null_mut())
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rdd_client<'a>(rdd: *const RDDDetection) -> *const RDDClient<'a> {
     let rdd = unsafe { &*rdd };
@@ -164,6 +260,64 @@ pub unsafe extern "C" fn rdd_client_browser_version(client: *const RDDClient) ->
         .unwrap_or(null_mut())
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn rdd_client_short_name(client: *const RDDClient) -> *mut c_char {
+    let client = unsafe { &*client };
+
+    client
+        .client
+        .as_ref()
+        .and_then(|client| client.short_name.as_deref())
+        .map(|short_name| CString::new(short_name).unwrap().into_raw())
+        .unwrap_or(null_mut())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rdd_client_url(client: *const RDDClient) -> *mut c_char {
+    let client = unsafe { &*client };
+
+    client
+        .client
+        .as_ref()
+        .and_then(|client| client.url.as_deref())
+        .map(|url| CString::new(url).unwrap().into_raw())
+        .unwrap_or(null_mut())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rdd_client_subtype(client: *const RDDClient) -> *mut c_char {
+    let client = unsafe { &*client };
+
+    client
+        .client
+        .as_ref()
+        .and_then(|client| client.subtype.as_deref())
+        .map(|subtype| CString::new(subtype).unwrap().into_raw())
+        .unwrap_or(null_mut())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rdd_client_browser_family(client: *const RDDClient) -> *mut c_char {
+    let client = unsafe { &*client };
+
+    client
+        .client
+        .as_ref()
+        .and_then(|client| client.family())
+        .map(|family| CString::new(family).unwrap().into_raw())
+        .unwrap_or(null_mut())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rdd_client_mobile_only(client: *const RDDClient) -> bool {
+    let client = unsafe { &*client };
+
+    client
+        .client
+        .as_ref()
+        .is_some_and(|client| client.mobile_only())
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rdd_device<'a>(rdd: *const RDDDetection) -> *const RDDDevice<'a> {
     let rdd = unsafe { &*rdd };
@@ -267,6 +421,17 @@ pub unsafe extern "C" fn rdd_os_family(os: *const RDDOS) -> *mut c_char {
         .unwrap_or(null_mut())
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn rdd_os_short_name(os: *const RDDOS) -> *mut c_char {
+    let os = unsafe { &*os };
+
+    os.os
+        .as_ref()
+        .and_then(|os| os.short_name.as_ref())
+        .map(|short_name| CString::new(short_name.as_str()).unwrap().into_raw())
+        .unwrap_or(null_mut())
+}
+
 // pub struct Bot {
 //     pub name: String,
 //     pub category: Option<String>,
@@ -355,6 +520,38 @@ pub unsafe extern "C" fn rdd_is_bot(rdd: *const RDDDetection) -> bool {
     rdd.dt.as_ref().map(|x| x.is_bot()).unwrap_or(false)
 }
 
+/// What drives an automated browser, eg. `headless chrome`, null for browsers used by
+/// a person.
+#[no_mangle]
+pub unsafe extern "C" fn rdd_automation(rdd: *const RDDDetection) -> *mut c_char {
+    let rdd = unsafe { &*rdd };
+
+    match &rdd.dt {
+        Some(Detection::Known(dev)) => dev
+            .automation
+            .map(|automation| CString::new(automation.as_str()).unwrap().into_raw())
+            .unwrap_or(null_mut()),
+        _ => null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rdd_is_automated(rdd: *const RDDDetection) -> bool {
+    let rdd = unsafe { &*rdd };
+
+    match &rdd.dt {
+        Some(Detection::Known(dev)) => dev.is_automated(),
+        _ => false,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rdd_is_reduced_ua(rdd: *const RDDDetection) -> bool {
+    let rdd = unsafe { &*rdd };
+
+    rdd.dt.as_ref().is_some_and(|x| x.reduced_ua())
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rdd_is_mobile(rdd: *const RDDDetection) -> bool {
     let rdd = unsafe { &*rdd };
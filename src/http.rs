@@ -7,33 +7,94 @@ use std::convert::Infallible;
 use std::net::SocketAddr;
 
 use crate::device_detector::DeviceDetector;
-use std::sync::Arc;
+use crate::error::DetectError;
+use crate::rule_database::RuleDatabaseBuilder;
+use std::sync::{Arc, RwLock};
+
+/// The detector currently serving requests, which can be swapped out for one using
+/// a freshly loaded rule database.
+struct SharedDetector {
+    current: RwLock<Arc<DeviceDetector>>,
+    rules: Option<RuleDatabaseBuilder>,
+    // only one reload at a time, so that a slow reload can't overwrite a newer one.
+    reloading: tokio::sync::Mutex<()>,
+}
+
+impl SharedDetector {
+    fn current(&self) -> Arc<DeviceDetector> {
+        self.current.read().unwrap().clone()
+    }
+
+    /// Reloads the rule database from disk and swaps it in once every regex compiles.
+    /// Requests already holding the old detector finish with it.
+    async fn reload(&self) -> Result<(), DetectError> {
+        let rules = self.rules.clone().ok_or_else(|| {
+            DetectError::rule_database("", "no rule files configured, there is nothing to reload")
+        })?;
+
+        let _reloading = self.reloading.lock().await;
+
+        let database = tokio::task::spawn_blocking(move || {
+            let database = rules.build()?;
+            database.validate()?;
+            Ok(database)
+        })
+        .await
+        .map_err(|err| DetectError::rule_database("", err))??;
+
+        let detector = self.current().rebuild_with_database(Arc::new(database));
+        *self.current.write().unwrap() = Arc::new(detector);
+
+        Ok(())
+    }
+}
 
 async fn serve_request(
     req: Request<Body>,
-    detector: Arc<DeviceDetector>,
+    detector: Arc<SharedDetector>,
 ) -> Result<Response<Body>> {
     match (req.method(), req.uri().path()) {
         (&Method::POST, "/detect") => {
+            let detector = detector.current();
+
             // TODO prevent pulling entire body into memory in case of abuse
             let body = hyper::body::to_bytes(req.into_body()).await?;
-            let body = String::from_utf8(body.to_vec())?;
-
-            let detection = detector.parse(&body, None);
-
-            let detection = detection.unwrap_or_else(|err| {
-                panic!("error: {:?} ua: {}", &err, &body);
-            });
+            let body = match String::from_utf8(body.to_vec()) {
+                Ok(body) => body,
+                Err(err) => {
+                    let err = DetectError::InvalidInput(format!("user agent is not utf8: {}", err));
+                    return error_response(&err);
+                }
+            };
+
+            let detection = match detector.parse(&body, None) {
+                Ok(detection) => detection,
+                Err(err) => {
+                    eprintln!("error: {} ua: {}", err, &body);
+                    return error_response(&err);
+                }
+            };
 
             let response = serde_json::to_string(&detection.to_value())?;
 
             Ok(Response::new(Body::from(response)))
         }
 
+        (&Method::POST, "/admin/reload") => match detector.reload().await {
+            Ok(()) => {
+                eprintln!("Rule database reloaded");
+                Ok(Response::new("OK\n".into()))
+            }
+            Err(err) => {
+                eprintln!("Rule database reload failed: {}", err);
+                error_response(&err)
+            }
+        },
+
         (&Method::GET, "/health") => Ok(Response::new("OK\n".into())),
 
         _route => {
-            let err = "valid routes:\n  POST /detect with a body containing referer\n  GET  /health for heartbeat";
+            let err = "valid routes:\n  POST /detect with a body containing referer\n  POST /admin/reload to reload the rule database\n  GET  /health for heartbeat";
             eprintln!("{}", err);
             Ok(Response::builder()
                 .status(StatusCode::NOT_FOUND)
@@ -42,16 +103,67 @@ async fn serve_request(
     }
 }
 
+// The error as json, with the same stable code the ffi interface reports.
+fn error_response(err: &DetectError) -> Result<Response<Body>> {
+    let status = match err {
+        _ if err.is_input_error() => StatusCode::BAD_REQUEST,
+        // the rules are fine, it is the user agent they can't be run on.
+        DetectError::RegexLimit { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+
+    let body = serde_json::json!({
+        "error": {
+            "code": err.code(),
+            "name": err.name(),
+            "message": err.to_string(),
+        }
+    });
+
+    Ok(Response::builder()
+        .status(status)
+        .body(Body::from(body.to_string()))?)
+}
+
 async fn shutdown_signal() {
     tokio::signal::ctrl_c()
         .await
         .expect("failed to install CTRL+C signal handler");
 }
 
-pub async fn server(listen_address: SocketAddr, device_detector: DeviceDetector) {
+#[cfg(unix)]
+async fn reload_on_sighup(detector: Arc<SharedDetector>) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangups =
+        signal(SignalKind::hangup()).expect("failed to install SIGHUP signal handler");
+
+    while hangups.recv().await.is_some() {
+        match detector.reload().await {
+            Ok(()) => eprintln!("Rule database reloaded"),
+            Err(err) => eprintln!("Rule database reload failed: {}", err),
+        }
+    }
+}
+
+/// Serves detections until interrupted. When `rules` is set, the rule database is
+/// built again from it, rereading its directory and overlay files, on
+/// `POST /admin/reload` or SIGHUP.
+pub async fn server(
+    listen_address: SocketAddr,
+    device_detector: DeviceDetector,
+    rules: Option<RuleDatabaseBuilder>,
+) {
     eprintln!("Listening on {}", listen_address);
 
-    let device_detector = Arc::new(device_detector);
+    let device_detector = Arc::new(SharedDetector {
+        current: RwLock::new(Arc::new(device_detector)),
+        rules,
+        reloading: tokio::sync::Mutex::new(()),
+    });
+
+    #[cfg(unix)]
+    tokio::spawn(reload_on_sighup(device_detector.clone()));
 
     let make_svc = make_service_fn(|_conn| {
         let device_detector = device_detector.clone();
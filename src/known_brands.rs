@@ -0,0 +1,2158 @@
+use std::collections::HashMap;
+
+// The brands upstream knows of, as named by its device rules and vendor fragments and
+// checked against the brands of its fixtures. Upstream's own list also gives each a
+// short code, which isn't vendored along with the rules, so there are none here.
+
+pub struct AvailableBrands {
+    brands_by_name: HashMap<String, AvailableBrand>,
+}
+
+#[derive(Clone, Debug)]
+pub struct AvailableBrand {
+    pub name: String,
+}
+
+impl Default for AvailableBrands {
+    fn default() -> Self {
+        let brands_by_name = available_brands()
+            .into_iter()
+            .map(|name| {
+                (
+                    name.replace(' ', "").to_lowercase(),
+                    AvailableBrand { name },
+                )
+            })
+            .collect::<HashMap<String, AvailableBrand>>();
+
+        Self { brands_by_name }
+    }
+}
+
+impl AvailableBrands {
+    /// The brand named `name`, ignoring case like upstream does, see
+    /// [`Self::search_by_name`] for a more forgiving search.
+    pub fn by_name(&self, name: &str) -> Option<&AvailableBrand> {
+        let name = name.to_lowercase();
+        self.brands_by_name
+            .get(&name.replace(' ', ""))
+            .filter(|brand| brand.name.to_lowercase() == name)
+    }
+
+    /// The brand named `name`, ignoring case and spaces, eg. `"sony ericsson"` for
+    /// `Sony Ericsson`.
+    pub fn search_by_name(&self, name: &str) -> Option<&AvailableBrand> {
+        let name = name.to_lowercase().replace(' ', "");
+        self.brands_by_name.get(&name)
+    }
+}
+
+fn available_brands() -> Vec<String> {
+    [
+        "10moons",
+        "2E",
+        "360",
+        "3GNET",
+        "3GO",
+        "3Q",
+        "4Good",
+        "4ife",
+        "5IVE",
+        "7 Mobile",
+        "7Ke tree",
+        "8848",
+        "A&K",
+        "A1",
+        "A95X",
+        "AAUW",
+        "Accent",
+        "Accesstyle",
+        "ACD",
+        "Ace",
+        "Aceline",
+        "Acepad",
+        "Acer",
+        "Acteck",
+        "actiMirror",
+        "Adreamer",
+        "Adronix",
+        "Advan",
+        "Advance",
+        "Advantage Air",
+        "AEEZO",
+        "AFFIX",
+        "AfriOne",
+        "AG Mobile",
+        "AGM",
+        "AI+",
+        "AIDATA",
+        "AileTV",
+        "Ainol",
+        "Airis",
+        "Airness",
+        "AIRON",
+        "Airpha",
+        "Airtel",
+        "Airties",
+        "AirTouch",
+        "AIS",
+        "Aiuto",
+        "Aiwa",
+        "Ajib",
+        "Akai",
+        "AKIRA",
+        "Alba",
+        "Alcatel",
+        "Alcor",
+        "ALDI NORD",
+        "ALDI SÜD",
+        "Alfawise",
+        "Alienware",
+        "Aligator",
+        "All Star",
+        "AllCall",
+        "AllDocube",
+        "allente",
+        "ALLINmobile",
+        "Allview",
+        "Allwinner",
+        "Alps",
+        "alpsmart",
+        "Altech UEC",
+        "Altibox",
+        "Altice",
+        "Altimo",
+        "altron",
+        "Altus",
+        "AMA",
+        "Amazon",
+        "Amazon Basics",
+        "AMCV",
+        "AMGOO",
+        "Amigoo",
+        "Amino",
+        "Amoi",
+        "ANBERNIC",
+        "ANCEL",
+        "andersson",
+        "Andowl",
+        "Angelcare",
+        "AngelTech",
+        "Anker",
+        "Anry",
+        "ANS",
+        "ANXONIT",
+        "AOC",
+        "Aocos",
+        "Aocwei",
+        "AOpen",
+        "Aoro",
+        "Aoson",
+        "AOYODKG",
+        "ApoloSign",
+        "Apple",
+        "Aquarius",
+        "Archos",
+        "Arian Space",
+        "Arival",
+        "Ark",
+        "ArmPhone",
+        "Arnova",
+        "ARRIS",
+        "Artel",
+        "Artizlee",
+        "ArtLine",
+        "Arçelik",
+        "Asano",
+        "Asanzo",
+        "Ask",
+        "Aspera",
+        "ASSE",
+        "Assistant",
+        "astro (MY)",
+        "Astro (UA)",
+        "Asus",
+        "AT&T",
+        "Athesi",
+        "Atlantic Electrics",
+        "Atmaca Elektronik",
+        "ATMAN",
+        "ATMPC",
+        "ATOL",
+        "Atom",
+        "Atouch",
+        "Atozee",
+        "Attila",
+        "Atvio",
+        "Audiovox",
+        "AUPO",
+        "AURIS",
+        "Autan",
+        "AUX",
+        "Avaya",
+        "Avenzo",
+        "AVH",
+        "Avvio",
+        "Awow",
+        "AWOX",
+        "AXEN",
+        "Axioo",
+        "AXXA",
+        "Axxion",
+        "AYA",
+        "AYYA",
+        "Azeyou",
+        "AZOM",
+        "Azumi Mobile",
+        "Azupik",
+        "b2m",
+        "Backcell",
+        "BAFF",
+        "BangOlufsen",
+        "Barnes & Noble",
+        "BARTEC",
+        "BAUHN",
+        "BB Mobile",
+        "BBK",
+        "BDF",
+        "BDQ",
+        "BDsharing",
+        "Beafon",
+        "Becker",
+        "Beeline",
+        "Beelink",
+        "Beetel",
+        "Beista",
+        "Beko",
+        "Bell",
+        "Bellphone",
+        "Benco",
+        "Benesse",
+        "BenQ",
+        "BenQ-Siemens",
+        "BenWee",
+        "Benzo",
+        "Beyond",
+        "Bezkam",
+        "BGH",
+        "Biegedy",
+        "Bigben",
+        "BIHEE",
+        "BilimLand",
+        "Billion",
+        "Billow",
+        "BioRugged",
+        "Bird",
+        "Bitel",
+        "Bitmore",
+        "Bittium",
+        "Bkav",
+        "Black Bear",
+        "Black Box",
+        "Black Fox",
+        "Blackpcs",
+        "Blackphone",
+        "Blackton",
+        "Blackview",
+        "Blaupunkt",
+        "Bleck",
+        "BLISS",
+        "Blloc",
+        "Blow",
+        "Blu",
+        "Bluboo",
+        "Bluebird",
+        "Bluedot",
+        "Bluegood",
+        "BlueSky",
+        "Bluewave",
+        "BluSlate",
+        "BMAX",
+        "Bmobile",
+        "BMW",
+        "BMXC",
+        "BNCF",
+        "Bobarry",
+        "bogo",
+        "Bolva",
+        "Bookeen",
+        "Boost",
+        "Botech",
+        "Boway",
+        "bq",
+        "Bqeel",
+        "BrandCode",
+        "Brandt",
+        "BRAVE",
+        "Bravis",
+        "BrightSign",
+        "Brigmton",
+        "Brondi",
+        "BROR",
+        "BS Mobile",
+        "Bubblegum",
+        "Bundy",
+        "Bush",
+        "BuzzTV",
+        "BYD",
+        "BYJU'S",
+        "BYYBUO",
+        "C Idea",
+        "C5 Mobile",
+        "CADENA",
+        "CAGI",
+        "Caixun",
+        "CALME",
+        "Camfone",
+        "Canaima",
+        "Canal Digital",
+        "Canal+",
+        "Canguro",
+        "Capitel",
+        "Captiva",
+        "Carbon Mobile",
+        "Carrefour",
+        "Casio",
+        "Casper",
+        "Cat",
+        "Cavion",
+        "CCIT",
+        "Cecotec",
+        "Ceibal",
+        "Celcus",
+        "Celkon",
+        "Cell-C",
+        "Cellacom",
+        "CellAllure",
+        "Cellution",
+        "CENTEK",
+        "Centric",
+        "CEPTER",
+        "CG Mobile",
+        "CGV",
+        "Chainway",
+        "Changhong",
+        "CHCNAV",
+        "Cherry Mobile",
+        "Chico Mobile",
+        "ChiliGreen",
+        "China Mobile",
+        "China Telecom",
+        "Chuwi",
+        "CipherLab",
+        "Citycall",
+        "CKK Mobile",
+        "Claresta",
+        "Clarmin",
+        "CLAYTON",
+        "ClearPHONE",
+        "Clementoni",
+        "Cloud Mobile",
+        "Cloudfone",
+        "Clout",
+        "Clovertek",
+        "CMF",
+        "CnM",
+        "Cobalt",
+        "Coby Kyros",
+        "Cogeco",
+        "COLORROOM",
+        "Colors",
+        "Comio",
+        "CommScope",
+        "Compal",
+        "Compaq",
+        "COMPUMAX",
+        "ComTrade Tesla",
+        "Conceptum",
+        "Concord",
+        "ConCorde",
+        "Condor",
+        "Connectce",
+        "Connex",
+        "Conquest",
+        "CONSUNG",
+        "Continental Edison",
+        "Contixo",
+        "coocaa",
+        "COOD-E",
+        "Coolpad",
+        "Coopers",
+        "CORN",
+        "Cosmote",
+        "Covia",
+        "Cowon",
+        "COYOTE",
+        "CPDEVICE",
+        "CreNova",
+        "Crescent",
+        "Crestron",
+        "Cricket",
+        "Crony",
+        "Crosscall",
+        "Crown",
+        "Ctroniq",
+        "Cube",
+        "CUBOT",
+        "CUD",
+        "Cuiud",
+        "Cultraview",
+        "CVTE",
+        "Cwowdefu",
+        "CX",
+        "Cyrus",
+        "D-Link",
+        "D-Tech",
+        "Daewoo",
+        "Danew",
+        "DangcapHD",
+        "Dany",
+        "Daria",
+        "DASS",
+        "Datalogic",
+        "Datamini",
+        "Datang",
+        "Datawind",
+        "Datsun",
+        "Dawlance",
+        "DbPhone",
+        "Dbtel",
+        "Dcode",
+        "DEALDIG",
+        "DEC",
+        "Dell",
+        "Denali",
+        "Denka",
+        "Denver",
+        "Desay",
+        "DeWalt",
+        "DEXP",
+        "DEYI",
+        "DF",
+        "DGTEC",
+        "DIALN",
+        "Dialog",
+        "Dicam",
+        "Digi",
+        "Digicel",
+        "DIGICOM",
+        "Digidragon",
+        "DIGIFORS",
+        "Digihome",
+        "Digiland",
+        "Digit",
+        "Digma",
+        "DIJITSU",
+        "DIKOM",
+        "DIMO",
+        "Dinalink",
+        "Dinax",
+        "DING DING",
+        "Diofox",
+        "DIORA",
+        "DISH",
+        "Disney",
+        "Ditecma",
+        "Diva",
+        "DiverMax",
+        "Divisat",
+        "DIXON",
+        "DL",
+        "DMOAO",
+        "DNS",
+        "DoCoMo",
+        "Doffler",
+        "Dolamee",
+        "Dom.ru",
+        "Doogee",
+        "Doopro",
+        "Doov",
+        "Dopod",
+        "Doppio",
+        "Dora",
+        "DORLAND",
+        "Doro",
+        "DPA",
+        "DRAGON",
+        "Dragon Touch",
+        "Dream Multimedia",
+        "Dreamgate",
+        "DreamStar",
+        "Droidlogic",
+        "Droxio",
+        "DSDevices",
+        "DSIC",
+        "Dtac",
+        "DUDU AUTO",
+        "Dune HD",
+        "DUNNS Mobile",
+        "DuoTV",
+        "Durabook",
+        "Duubee",
+        "Dykemann",
+        "Dyon",
+        "E-Boda",
+        "E-Ceros",
+        "E-TACHI",
+        "E-tel",
+        "Eagle",
+        "EagleSoar",
+        "EAS Electric",
+        "Easypix",
+        "EBEN",
+        "EBEST",
+        "Echo Mobiles",
+        "ecom",
+        "ECON",
+        "ECOO",
+        "EcoStar",
+        "ECS",
+        "Edanix",
+        "Edenwood",
+        "EE",
+        "EFT",
+        "EGL",
+        "EGOTEK",
+        "Ehlel",
+        "Einstein",
+        "EKO",
+        "Eks Mobility",
+        "EKT",
+        "ELARI",
+        "ELE-GATE",
+        "Elecson",
+        "Electroneum",
+        "ELECTRONIA",
+        "Elekta",
+        "Elektroland",
+        "Element",
+        "Elenberg",
+        "Elephone",
+        "Elevate",
+        "Elista",
+        "elit",
+        "Elong Mobile",
+        "Eltex",
+        "Ematic",
+        "Emporia",
+        "ENACOM",
+        "ENDURO",
+        "Energizer",
+        "Energy Sistem",
+        "Engel",
+        "ENIE",
+        "Enot",
+        "eNOVA",
+        "Entity",
+        "Envizen",
+        "Ephone",
+        "Epic",
+        "EPIK Learning",
+        "Epik One",
+        "Eplutus",
+        "Epson",
+        "Equator",
+        "Ergo",
+        "Ericsson",
+        "Erisson",
+        "Essential",
+        "Essentielb",
+        "eSTAR",
+        "ETOE",
+        "Eton",
+        "eTouch",
+        "Etuline",
+        "Eudora",
+        "Eurocase",
+        "EUROLUX",
+        "Eurostar",
+        "Evercoss",
+        "Everest",
+        "Everex",
+        "Everfine",
+        "Everis",
+        "Evertek",
+        "Evolio",
+        "Evolveo",
+        "Evoo",
+        "EVPAD",
+        "EvroMedia",
+        "evvoli",
+        "EWIS",
+        "EXCEED",
+        "Exertis",
+        "Exmart",
+        "ExMobile",
+        "EXO",
+        "Explay",
+        "Express LUCK",
+        "ExtraLink",
+        "Extrem",
+        "Eyemoo",
+        "EYU",
+        "Ezio",
+        "Ezze",
+        "F&U",
+        "F+",
+        "F150",
+        "F2 Mobile",
+        "Facebook",
+        "Facetel",
+        "Facime",
+        "Fairphone",
+        "Famoco",
+        "Famous",
+        "Fantec",
+        "Fanvace",
+        "FaRao Pro",
+        "Farassoo",
+        "FarEasTone",
+        "Fengxiang",
+        "Fenoti",
+        "FEONAL",
+        "Fero",
+        "FFF SmartLife",
+        "Figgers",
+        "FiGi",
+        "FiGO",
+        "FiiO",
+        "Filimo",
+        "FILIX",
+        "FinePower",
+        "FINIX",
+        "Finlux",
+        "FireFly Mobile",
+        "FISE",
+        "Fision",
+        "FITCO",
+        "Fluo",
+        "Fly",
+        "FLYCAT",
+        "FLYCOAY",
+        "FMT",
+        "FNB",
+        "FNF",
+        "Fobem",
+        "Fondi",
+        "Fonos",
+        "FONTEL",
+        "FOODO",
+        "FORME",
+        "Formovie",
+        "Formuler",
+        "Forstar",
+        "Fortis",
+        "FortuneShip",
+        "FOSSiBOT",
+        "Four Mobile",
+        "Fourel",
+        "FOX",
+        "Foxconn",
+        "FoxxD",
+        "FPT",
+        "free",
+        "Freetel",
+        "FreeYond",
+        "FRESH",
+        "Frunsi",
+        "Fuego",
+        "FUJICOM",
+        "Fujitsu",
+        "Funai",
+        "Fusion5",
+        "Future Mobile Technology",
+        "Fxtec",
+        "G-Guard",
+        "G-PLUS",
+        "G-Tab",
+        "G-TiDE",
+        "G-Touch",
+        "G-Vill",
+        "Galactic",
+        "Galatec",
+        "Galaxy Innovations",
+        "Gamma",
+        "Garmin-Asus",
+        "Gateway",
+        "Gazal",
+        "Gazer",
+        "GDL",
+        "Geanee",
+        "Geant",
+        "Gear Mobile",
+        "Gemini",
+        "General Mobile",
+        "Genesis",
+        "Genius Devices",
+        "Geo Phone",
+        "GEOFOX",
+        "Geotel",
+        "Geotex",
+        "GEOZON",
+        "Getnord",
+        "GFive",
+        "Gfone",
+        "Ghia",
+        "Ghong",
+        "Ghost",
+        "Gigabyte",
+        "Gigaset",
+        "Gini",
+        "Ginzzu",
+        "Gionee",
+        "GIRASOLE",
+        "GlobalSec",
+        "Globex",
+        "Globmall",
+        "GlocalMe",
+        "Glofiish",
+        "GLONYX",
+        "Glory Star",
+        "GLX",
+        "GN Electronics",
+        "GOCLEVER",
+        "Gocomma",
+        "GoGEN",
+        "Gol Mobile",
+        "GOLDBERG",
+        "GoldMaster",
+        "GoldStar",
+        "Goly",
+        "Gome",
+        "GoMobile",
+        "GOODTEL",
+        "Google",
+        "Goophone",
+        "Gooweel",
+        "GOtv",
+        "Gplus",
+        "Gradiente",
+        "Graetz",
+        "Grape",
+        "Great Asia",
+        "Gree",
+        "Green Lion",
+        "Green Orange",
+        "Greentel",
+        "Gresso",
+        "Gretel",
+        "GroBerwert",
+        "Grundig",
+        "Grünberg",
+        "Gtel",
+        "GTMEDIA",
+        "GTX",
+        "Guophone",
+        "GVC Pro",
+        "H96",
+        "Hafury",
+        "Haier",
+        "Haipai",
+        "Haixu",
+        "Hamlet",
+        "Hammer",
+        "Handheld",
+        "HannSpree",
+        "Hanseatic",
+        "Hanson",
+        "HAOQIN",
+        "HAOVM",
+        "Hardkernel",
+        "Harper",
+        "Hartens",
+        "Hasee",
+        "Hathway",
+        "HAVIT",
+        "HDC",
+        "HeadWolf",
+        "HEC",
+        "Heimat",
+        "Helio",
+        "Hemilton",
+        "HERO",
+        "HexaByte",
+        "Hezire",
+        "Hi",
+        "Hi Nova",
+        "Hi-Level",
+        "Hiberg",
+        "HiBy",
+        "HIGH1ONE",
+        "Highscreen",
+        "HiGrace",
+        "HiHi",
+        "HiKing",
+        "HiMax",
+        "HIPER",
+        "Hipstreet",
+        "Hiremco",
+        "Hisense",
+        "Hitachi",
+        "Hitech",
+        "HKC",
+        "HKPro",
+        "HMD",
+        "hoco",
+        "HOFER",
+        "Hoffmann",
+        "HOLLEBERG",
+        "Homatics",
+        "Hometech",
+        "HOMII",
+        "Homtom",
+        "Honeywell",
+        "HongTop",
+        "HONKUAHG",
+        "Honor",
+        "Hoozo",
+        "Hopeland",
+        "Horion",
+        "Horizon",
+        "Horizont",
+        "Hosin",
+        "Hot Pepper",
+        "HOTACK",
+        "Hotel TV Company",
+        "HOTREALS",
+        "Hotwav",
+        "How",
+        "HP",
+        "HTC",
+        "Huadoo",
+        "Huagan",
+        "Huavi",
+        "Huawei",
+        "Hugerock",
+        "Humanware",
+        "Humax",
+        "HUMElab",
+        "Hurricane",
+        "Huskee",
+        "Hyatta",
+        "Hykker",
+        "Hyrican",
+        "Hytera",
+        "Hyundai",
+        "Hyve",
+        "I KALL",
+        "i-Cherry",
+        "I-INN",
+        "i-Joy",
+        "i-mate",
+        "i-mobile",
+        "I-Plus",
+        "iBall",
+        "iBerry",
+        "ibowin",
+        "iBrit",
+        "IconBIT",
+        "Icone Gold",
+        "iData",
+        "IDC",
+        "iDino",
+        "iDroid",
+        "iFIT",
+        "iGet",
+        "iHome Life",
+        "iHunt",
+        "Ikea",
+        "IKI Mobile",
+        "iKoMo",
+        "iKon",
+        "iKonia",
+        "IKU Mobile",
+        "iLA",
+        "iLepo",
+        "iLife",
+        "iMan",
+        "Imaq",
+        "iMars",
+        "iMI",
+        "IMO Mobile",
+        "Imose",
+        "Impression",
+        "iMuz",
+        "iNavi",
+        "INCAR",
+        "Inch",
+        "Inco",
+        "Indurama",
+        "iNew",
+        "Infiniton",
+        "InfinityPro",
+        "Infinix",
+        "InFocus",
+        "InfoKit",
+        "Infomir",
+        "InFone",
+        "Inhon",
+        "Inka",
+        "Inkti",
+        "InnJoo",
+        "Inno Hit",
+        "Innos",
+        "Innostream",
+        "iNo Mobile",
+        "Inoi",
+        "iNOVA",
+        "inovo",
+        "INQ",
+        "Insignia",
+        "INSYS",
+        "Intek",
+        "Intel",
+        "Intex",
+        "Invens",
+        "Inverto",
+        "Invin",
+        "iOcean",
+        "IOTWE",
+        "iOutdoor",
+        "iPEGTOP",
+        "iPro",
+        "iQ&T",
+        "IQM",
+        "IRA",
+        "Irbis",
+        "iReplace",
+        "Iris",
+        "iRobot",
+        "iRola",
+        "iRulu",
+        "iSafe Mobile",
+        "iStar",
+        "iSWAG",
+        "IT",
+        "iTel",
+        "iTruck",
+        "IUNI",
+        "iVA",
+        "iView",
+        "iVooMi",
+        "ivvi",
+        "iWaylink",
+        "iXTech",
+        "iYou",
+        "iZotron",
+        "Jambo",
+        "JAY-Tech",
+        "Jckkcfug",
+        "Jeep",
+        "Jeka",
+        "Jesy",
+        "JFone",
+        "Jiake",
+        "Jiayu",
+        "Jide",
+        "Jin Tu",
+        "Jinga",
+        "Jio",
+        "Jivi",
+        "JKL",
+        "Jolla",
+        "Joy",
+        "JoySurf",
+        "JPay",
+        "JREN",
+        "Jumper",
+        "Juniper Systems",
+        "Just5",
+        "JUSYEA",
+        "JVC",
+        "JXD",
+        "K-Lite",
+        "K-Touch",
+        "Kaan",
+        "Kaiomy",
+        "Kalley",
+        "Kanji",
+        "KAP",
+        "Kapsys",
+        "Karbonn",
+        "Kata",
+        "KATV1",
+        "Kazam",
+        "Kazuna",
+        "KDDI",
+        "Kempler & Strauss",
+        "Kenbo",
+        "Kendo",
+        "Keneksi",
+        "KENSHI",
+        "KENWOOD",
+        "Kenxinda",
+        "KGTEL",
+        "Khadas",
+        "Kiano",
+        "kidiby",
+        "Kingbox",
+        "Kingelon",
+        "Kingstar",
+        "Kingsun",
+        "KINGZONE",
+        "Kinstone",
+        "Kiowa",
+        "Kivi",
+        "Klipad",
+        "KMC",
+        "KN Mobile",
+        "Kocaso",
+        "Kodak",
+        "Kogan",
+        "Komu",
+        "Konka",
+        "Konrow",
+        "Koobee",
+        "Koolnee",
+        "Kooper",
+        "KOPO",
+        "Korax",
+        "Koridy",
+        "Koslam",
+        "Kraft",
+        "KREZ",
+        "KRIP",
+        "KRONO",
+        "Krüger&Matz",
+        "KT-Tech",
+        "KTC",
+        "KUBO",
+        "KuGou",
+        "Kuliao",
+        "Kult",
+        "Kumai",
+        "Kurio",
+        "KVADRA",
+        "Kvant",
+        "Kydos",
+        "Kyocera",
+        "Kyowon",
+        "Kzen",
+        "KZG",
+        "L-Max",
+        "LAGENIO",
+        "LAIQ",
+        "Land Rover",
+        "Landvo",
+        "Lanin",
+        "Lanix",
+        "Lark",
+        "Laser",
+        "Laurus",
+        "Lava",
+        "LCT",
+        "Le Pan",
+        "Leader Phone",
+        "Leagoo",
+        "Leben",
+        "LeBest",
+        "Lectrus",
+        "Ledstar",
+        "LeEco",
+        "Leelbox",
+        "Leff",
+        "Legend",
+        "Leke",
+        "Lemco",
+        "LEMFO",
+        "Lemhoov",
+        "Lenco",
+        "Lenovo",
+        "Leotec",
+        "Lephone",
+        "Lesia",
+        "Lexand",
+        "Lexibook",
+        "LG",
+        "Liberton",
+        "Lifemaxx",
+        "Lime",
+        "Lingbo",
+        "Lingwin",
+        "Linnex",
+        "Linsar",
+        "Linsay",
+        "Listo",
+        "LNMBBS",
+        "Loewe",
+        "LOGAN",
+        "Logic",
+        "Logic Instrument",
+        "Logicom",
+        "Logik",
+        "Logitech",
+        "LOKMAT",
+        "LongTV",
+        "Loview",
+        "Lovme",
+        "LT Mobile",
+        "Lumigon",
+        "Lumitel",
+        "Lumus",
+        "Luna",
+        "LUNNEN",
+        "LUO",
+        "Luxor",
+        "Lville",
+        "LW",
+        "LYF",
+        "LYOTECH LABS",
+        "M-Horse",
+        "M-KOPA",
+        "M-Tech",
+        "M.T.T.",
+        "M3 Mobile",
+        "M4tel",
+        "MAC AUDIO",
+        "Macoox",
+        "Mafe",
+        "MAG",
+        "MAGCH",
+        "Magenta",
+        "Magicsee",
+        "Magnus",
+        "Majestic",
+        "Malata",
+        "Mango",
+        "Manhattan",
+        "Mann",
+        "Manta Multimedia",
+        "Mantra",
+        "Mara",
+        "Marshal",
+        "Mascom",
+        "Massgo",
+        "Masstel",
+        "Master-G",
+        "Mastertech",
+        "Matco Tools",
+        "Matrix",
+        "Maunfeld",
+        "Maxcom",
+        "Maxfone",
+        "Maximus",
+        "Maxtron",
+        "MAXVI",
+        "Maxwell",
+        "Maxwest",
+        "MAXX",
+        "Maze",
+        "Maze Speed",
+        "MBI",
+        "MBK",
+        "MBOX",
+        "McLaut",
+        "MDC Store",
+        "meanIT",
+        "Mecer",
+        "MECHEN",
+        "Mecool",
+        "Mediacom",
+        "Medion",
+        "MEEG",
+        "MEGA VISION",
+        "Megacable",
+        "MegaFon",
+        "MEGAMAX",
+        "Meitu",
+        "Meizu",
+        "Melrose",
+        "MeMobile",
+        "Memup",
+        "MEO",
+        "MESWAO",
+        "Meta",
+        "Metz",
+        "MEU",
+        "Microlab",
+        "MicroMax",
+        "Microsoft",
+        "Microtech",
+        "Mightier",
+        "MIIA",
+        "Minix",
+        "Mint",
+        "Mintt",
+        "Mio",
+        "Mione",
+        "mipo",
+        "Miray",
+        "Mitchell & Brown",
+        "Mito",
+        "Mitsubishi",
+        "Mitsui",
+        "MIVO",
+        "MIWANG",
+        "MIXC",
+        "MiXzo",
+        "MLLED",
+        "MLS",
+        "Mobell",
+        "Mobicel",
+        "MobiIoT",
+        "Mobiistar",
+        "Mobile Kingdom",
+        "Mobiola",
+        "Mobistel",
+        "MobiWire",
+        "Mobo",
+        "Mobvoi",
+        "Mode 1",
+        "Mode Mobile",
+        "Modecom",
+        "Mofut",
+        "Moondrop",
+        "MORTAL",
+        "Mosimosi",
+        "Motiv",
+        "Motorola",
+        "Motorola Solutions",
+        "Movic",
+        "MOVISUN",
+        "Movitel",
+        "Moxee",
+        "mPhone",
+        "Mpman",
+        "MSI",
+        "MStar",
+        "MTC",
+        "MTN",
+        "multibox",
+        "Multilaser",
+        "MultiPOS",
+        "MULTYNET",
+        "MwalimuPlus",
+        "MYFON",
+        "MyGica",
+        "MygPad",
+        "Mymaga",
+        "MyMobile",
+        "MyPhone (PH)",
+        "myPhone (PL)",
+        "Myria",
+        "Myros",
+        "Mystery",
+        "MyTab",
+        "MyWigo",
+        "N-one",
+        "Nabi",
+        "NABO",
+        "Nanho",
+        "Naomi Phone",
+        "NASCO",
+        "National",
+        "Navcity",
+        "Navitech",
+        "Navitel",
+        "Navon",
+        "NavRoad",
+        "NEC",
+        "Necnot",
+        "Nedaphone",
+        "Neffos",
+        "NEKO",
+        "Neo",
+        "neoCore",
+        "Neolix",
+        "Neomi",
+        "Neon IQ",
+        "Neoregent",
+        "Nesons",
+        "NetBox",
+        "Netgear",
+        "Netmak",
+        "NETWIT",
+        "NeuImage",
+        "NeuTab",
+        "NEVIR",
+        "New Balance",
+        "New Bridge",
+        "Newal",
+        "Newgen",
+        "Newland",
+        "Newman",
+        "Newsday",
+        "NewsMy",
+        "Nexa",
+        "Nexar",
+        "NEXBOX",
+        "Nexian",
+        "NEXON",
+        "NEXT",
+        "Next & NextStar",
+        "Nextbit",
+        "NextBook",
+        "NextTab",
+        "NG Optics",
+        "NGM",
+        "NGpon",
+        "Nikon",
+        "NILAIT",
+        "NINETEC",
+        "NINETOLOGY",
+        "Nintendo",
+        "nJoy",
+        "NOA",
+        "Noain",
+        "Nobby",
+        "Noblex",
+        "NOBUX",
+        "noDROPOUT",
+        "NOGA",
+        "Nokia",
+        "Nomi",
+        "Nomu",
+        "Noontec",
+        "Nordfrost",
+        "Nordmende",
+        "NORMANDE",
+        "NorthTech",
+        "Nos",
+        "Nothing",
+        "Nous",
+        "Novacom",
+        "Novex",
+        "Novey",
+        "NOVIS",
+        "NoviSea",
+        "NOVO",
+        "NTT West",
+        "NuAns",
+        "Nubia",
+        "NUU Mobile",
+        "NuVision",
+        "Nuvo",
+        "Nvidia",
+        "NYX Mobile",
+        "O+",
+        "O2",
+        "Oale",
+        "Oangcc",
+        "OASYS",
+        "Obabox",
+        "Ober",
+        "Obi",
+        "OCEANIC",
+        "Odotpad",
+        "Odys",
+        "Oilsky",
+        "OINOM",
+        "ok.",
+        "Okapi",
+        "Okapia",
+        "Oking",
+        "OKSI",
+        "OKWU",
+        "Olax",
+        "Olkya",
+        "Ollee",
+        "OLTO",
+        "Olympia",
+        "OMIX",
+        "Onda",
+        "OneClick",
+        "OneLern",
+        "OnePlus",
+        "Onida",
+        "Onix",
+        "Onkyo",
+        "ONN",
+        "ONVO",
+        "ONYX BOOX",
+        "Ookee",
+        "Ooredoo",
+        "OpelMobile",
+        "Openbox",
+        "Ophone",
+        "OPPO",
+        "Opsson",
+        "Optoma",
+        "Orange",
+        "Orange Pi",
+        "Orava",
+        "Orbic",
+        "Orbita",
+        "Orbsmart",
+        "Ordissimo",
+        "Orion",
+        "OSCAL",
+        "OTT",
+        "OTTO",
+        "OUJIA",
+        "Ouki",
+        "Oukitel",
+        "OUYA",
+        "Overmax",
+        "Ovvi",
+        "Owwo",
+        "OX TAB",
+        "OYSIN",
+        "Oysters",
+        "Oyyu",
+        "OzoneHD",
+        "Pacific Research Alliance",
+        "Packard Bell",
+        "PAGRAER",
+        "Paladin",
+        "Palm",
+        "Panacom",
+        "Panasonic",
+        "Panavox",
+        "Pano",
+        "Panodic",
+        "Panoramic",
+        "Pantech",
+        "PAPYRE",
+        "Parrot Mobile",
+        "Partner Mobile",
+        "PC Smart",
+        "PCBOX",
+        "PCD",
+        "PCD Argentina",
+        "PEAQ",
+        "Pelitt",
+        "Pendoo",
+        "Penta",
+        "Pentagram",
+        "Perfeo",
+        "Phicomm",
+        "Philco",
+        "Philips",
+        "Phonemax",
+        "phoneOne",
+        "Pico",
+        "PINE64",
+        "Pioneer",
+        "Pioneer Computers",
+        "PiPO",
+        "PIRANHA",
+        "Pixela",
+        "Pixelphone",
+        "PIXPRO",
+        "Pixus",
+        "Planet Computers",
+        "Platoon",
+        "Play Now",
+        "PLDT",
+        "Ployer",
+        "Plum",
+        "PlusStyle",
+        "Pluzz",
+        "PocketBook",
+        "POCO",
+        "Point Mobile",
+        "Point of View",
+        "Polar",
+        "PolarLine",
+        "Polaroid",
+        "Polestar",
+        "PolyPad",
+        "Polytron",
+        "Pomp",
+        "Poppox",
+        "POPTEL",
+        "Porsche",
+        "Portfolio",
+        "Positivo",
+        "Positivo BGH",
+        "Powerway",
+        "PPDS",
+        "PPTV",
+        "PREMIER",
+        "Premier Star",
+        "Premio",
+        "Prestigio",
+        "PRIME",
+        "Primepad",
+        "Primux",
+        "PRISM+",
+        "Pritom",
+        "Prixton",
+        "PROFiLO",
+        "Proline",
+        "Prology",
+        "ProScan",
+        "PROSONIC",
+        "Protruly",
+        "ProVision",
+        "PULID",
+        "Punos",
+        "Purism",
+        "PVBox",
+        "Q-Box",
+        "Q-Touch",
+        "Q.Bell",
+        "QFX",
+        "Qilive",
+        "QIN",
+        "Qiuwoky",
+        "QLink",
+        "QMobile",
+        "Qnet Mobile",
+        "QTECH",
+        "Qtek",
+        "Quanta Computer",
+        "Quantum",
+        "Quatro",
+        "Qubo",
+        "Quechua",
+        "Quest",
+        "Quipus",
+        "Qumo",
+        "Qupi",
+        "Qware",
+        "QWATT",
+        "R-TV",
+        "R3Di",
+        "Rakuten",
+        "Ramos",
+        "Raspberry",
+        "Ravoz",
+        "Raylandz",
+        "Razer",
+        "RAZZ",
+        "RCA Tablets",
+        "RCT",
+        "Reach",
+        "Readboy",
+        "Realix",
+        "Realme",
+        "RED",
+        "RED-X",
+        "Redbean",
+        "Redfox",
+        "RedLine",
+        "Redway",
+        "Reeder",
+        "REGAL",
+        "RelNAT",
+        "Relndoo",
+        "Remdun",
+        "Renova",
+        "RENSO",
+        "rephone",
+        "Retroid Pocket",
+        "Revo",
+        "Revomovil",
+        "Rhino",
+        "Ricoh",
+        "Rikomagic",
+        "RIM",
+        "Ringing Bells",
+        "Rinno",
+        "Ritmix",
+        "Ritzviva",
+        "Riviera",
+        "Rivo",
+        "Rizzen",
+        "ROADMAX",
+        "Roadrover",
+        "Roam Cat",
+        "ROCH",
+        "Rocket",
+        "Rokit",
+        "Roku",
+        "Rombica",
+        "Romsat",
+        "Ross&Moor",
+        "Rover",
+        "Rover Computers",
+        "Royole",
+        "RoyQueen",
+        "RT Project",
+        "RTK",
+        "RugGear",
+        "RuggeTech",
+        "Ruggex",
+        "Ruio",
+        "Runbo",
+        "RunGee",
+        "Rupa",
+        "Ryte",
+        "S-Color",
+        "S-TELL",
+        "S2Tel",
+        "Saba",
+        "Safaricom",
+        "Sagem",
+        "Sagemcom",
+        "Saiet",
+        "SAILF",
+        "Salora",
+        "Sambox",
+        "Samsung",
+        "Samtech",
+        "Samtron",
+        "Sanei",
+        "Sankey",
+        "Sansui",
+        "Santin",
+        "SANY",
+        "Sanyo",
+        "Savio",
+        "Sber",
+        "SCHAUB LORENZ",
+        "Schneider",
+        "Schok",
+        "SCHONTECH",
+        "Scoole",
+        "Scosmos",
+        "Seatel",
+        "SEBBE",
+        "Seeken",
+        "SEEWO",
+        "SEG",
+        "Sega",
+        "SEHMAX",
+        "Selecline",
+        "Selenga",
+        "Selevision",
+        "Selfix",
+        "SEMP TCL",
+        "Sencor",
+        "Sencrom",
+        "Sendo",
+        "Senkatel",
+        "SENNA",
+        "Senseit",
+        "Senwa",
+        "SERVO",
+        "Seuic",
+        "Sewoo",
+        "SFR",
+        "SGIN",
+        "Shanling",
+        "Sharp",
+        "Shift Phones",
+        "Shivaki",
+        "Shtrikh-M",
+        "Shuttle",
+        "Sico",
+        "Siemens",
+        "Sigma",
+        "Silelis",
+        "Silent Circle",
+        "Silva Schneider",
+        "Simbans",
+        "simfer",
+        "Simply",
+        "SINGER",
+        "Singtech",
+        "Siragon",
+        "Sirin Labs",
+        "Siswoo",
+        "SK Broadband",
+        "SKG",
+        "SKK Mobile",
+        "Sky",
+        "Skyline",
+        "SkyStream",
+        "Skytech",
+        "Skyworth",
+        "Smadl",
+        "Smailo",
+        "Smart",
+        "Smart Electronic",
+        "Smart Kassel",
+        "Smart Tech",
+        "Smartab",
+        "SmartBook",
+        "SMARTEC",
+        "Smartex",
+        "Smartfren",
+        "Smartisan",
+        "Smarty",
+        "Smooth Mobile",
+        "Smotreshka",
+        "SMT Telecom",
+        "SMUX",
+        "SNAMI",
+        "SobieTech",
+        "Soda",
+        "Softbank",
+        "Soho Style",
+        "Solas",
+        "SOLE",
+        "SOLO",
+        "Solone",
+        "Sonim",
+        "SONOS",
+        "Sony",
+        "Sony Ericsson",
+        "SOSH",
+        "SoulLink",
+        "Soundmax",
+        "SOWLY",
+        "Soyes",
+        "Spark NZ",
+        "Sparx",
+        "SPC",
+        "Spectralink",
+        "Spectrum",
+        "Spice",
+        "Spider",
+        "SPURT",
+        "SQOOL",
+        "SSKY",
+        "Ssmart",
+        "Star-Light",
+        "Starlight",
+        "Starmobile",
+        "Starway",
+        "Starwind",
+        "STF Mobile",
+        "STG Telecom",
+        "Stilevs",
+        "STK",
+        "Stonex",
+        "Storex",
+        "StrawBerry",
+        "StreamSystem",
+        "STRONG",
+        "Stylo",
+        "SUAAT",
+        "Subor",
+        "Sugar",
+        "SULPICE TV",
+        "Sumvision",
+        "SUNGATE",
+        "Sunmax",
+        "Sunmi",
+        "Sunny",
+        "Sunstech",
+        "SunVan",
+        "Sunvell",
+        "SUNWIND",
+        "Super General",
+        "SuperBOX",
+        "Supermax",
+        "SuperSonic",
+        "SuperTab",
+        "SuperTV",
+        "Supra",
+        "Supraim",
+        "Surfans",
+        "Surge",
+        "Suzuki",
+        "Sveon",
+        "Swipe",
+        "SWISSMOBILITY",
+        "Swisstone",
+        "Switel",
+        "SWOFY",
+        "Syco",
+        "SYH",
+        "Sylvania",
+        "Symphony",
+        "Syrox",
+        "System76",
+        "SZ TPS",
+        "T-Mobile",
+        "T96",
+        "TADAAM",
+        "TAG Tech",
+        "Taiga System",
+        "Takara",
+        "TALBERG",
+        "Talius",
+        "Tambo",
+        "Tanix",
+        "TAUBE",
+        "TB Touch",
+        "TCL",
+        "TCL SCBC",
+        "TD Systems",
+        "TD Tech",
+        "TeachTouch",
+        "Techmade",
+        "Technicolor",
+        "Technika",
+        "TechniSat",
+        "Technopc",
+        "TECHNOSAT",
+        "TechnoTrend",
+        "TechPad",
+        "Techstorm",
+        "Techwood",
+        "Teclast",
+        "Tecno Mobile",
+        "TecToy",
+        "TEENO",
+        "Teknosa",
+        "Tele2",
+        "Telefunken",
+        "Telego",
+        "Telenor",
+        "Telia",
+        "Telit",
+        "Telkom",
+        "Telly",
+        "Telma",
+        "TeloSystems",
+        "Telpo",
+        "Temigereev",
+        "TENPLUS",
+        "Teracube",
+        "Terra",
+        "Tesco",
+        "Tesla",
+        "TETC",
+        "Tetratab",
+        "teXet",
+        "ThL",
+        "Thomson",
+        "Thuraya",
+        "TIANYU",
+        "Tibuta",
+        "Tigers",
+        "Time2",
+        "Timovi",
+        "TIMvision",
+        "Tinai",
+        "Tinmo",
+        "TiPhone",
+        "Tivax",
+        "TiVo",
+        "TJC",
+        "TJD",
+        "TOKYO",
+        "Tolino",
+        "Tone",
+        "TOOGO",
+        "Tooky",
+        "Top House",
+        "Top-Tech",
+        "TopDevice",
+        "TOPDON",
+        "Topelotek",
+        "Toplux",
+        "TOPSHOWS",
+        "Topsion",
+        "Topway",
+        "Torex",
+        "TORNADO",
+        "Torque",
+        "TOSCIDO",
+        "Toshiba",
+        "Touch Plus",
+        "Touchmate",
+        "TOX",
+        "Transpeed",
+        "Trecfone",
+        "TrekStor",
+        "Trevi",
+        "TriaPlay",
+        "Tricolor",
+        "Trident",
+        "Trifone",
+        "Trimble",
+        "Trio",
+        "Tronsmart",
+        "True",
+        "True Slim",
+        "Tsinghua Tongfang",
+        "TTEC",
+        "TTfone",
+        "TTK-TV",
+        "TuCEL",
+        "TUCSON",
+        "Tunisie Telecom",
+        "Turbo",
+        "Turbo-X",
+        "TurboKids",
+        "TurboPad",
+        "Turkcell",
+        "Tuvio",
+        "TV+",
+        "TVC",
+        "TwinMOS",
+        "TWM",
+        "TWZ Corporation",
+        "TYD",
+        "Tymes",
+        "Türk Telekom",
+        "Türksat",
+        "U-Magic",
+        "U.S. Cellular",
+        "UD",
+        "UGINE",
+        "Ugoos",
+        "Uhans",
+        "Uhappy",
+        "Ulefone",
+        "Umax",
+        "UMIDIGI",
+        "Umiio",
+        "Unblock Tech",
+        "Uniden",
+        "Unihertz",
+        "Unikalne Smartphones",
+        "Unimax",
+        "Uniqcell",
+        "Uniscope",
+        "Unistrong",
+        "Unitech",
+        "UNITED",
+        "United Group",
+        "UNIWA",
+        "Unnecto",
+        "Unnion Technologies",
+        "UNNO",
+        "Unonu",
+        "UnoPhone",
+        "Unowhy",
+        "UOOGOU",
+        "Urovo",
+        "UTime",
+        "UTOK",
+        "UTStarcom",
+        "UZ Mobile",
+        "V-Gen",
+        "V-HOME",
+        "V-HOPE",
+        "v-mobile",
+        "V7",
+        "VAIO",
+        "VALE",
+        "VALEM",
+        "VALTECH",
+        "VANGUARD",
+        "Vankyo",
+        "VANWIN",
+        "Vargo",
+        "VASOUN",
+        "Vastking",
+        "VAVA",
+        "VC",
+        "VDVD",
+        "Vega",
+        "Veidoo",
+        "Vekta",
+        "Venso",
+        "Venstar",
+        "Venturer",
+        "VEON",
+        "Verico",
+        "Verizon",
+        "Vernee",
+        "Verssed",
+        "Versus",
+        "Vertex",
+        "Vertu",
+        "Verykool",
+        "Vesta",
+        "Vestel",
+        "VETAS",
+        "Vexia",
+        "VGO TEL",
+        "ViBox",
+        "Victurio",
+        "VIDA",
+        "Videocon",
+        "Videoweb",
+        "Viendo",
+        "ViewSonic",
+        "VIIPOO",
+        "VIKUSHA",
+        "VILLAON",
+        "VIMOQ",
+        "Vinabox",
+        "Vinga",
+        "Vinsoc",
+        "Vios",
+        "Viper",
+        "Vipro",
+        "Virzo",
+        "Vision Technology",
+        "Vision Touch",
+        "Visitech",
+        "Visual Land",
+        "Vitelcom",
+        "Vitumi",
+        "Vityaz",
+        "Viumee",
+        "Vivax",
+        "VIVIBright",
+        "VIVIMAGE",
+        "Vivo",
+        "VIWA",
+        "Vizio",
+        "Vizmo",
+        "VK Mobile",
+        "VKworld",
+        "VNPT Technology",
+        "VOCAL",
+        "Vodacom",
+        "Vodafone",
+        "VOGA",
+        "VOIX",
+        "VOLIA",
+        "VOLKANO",
+        "Volla",
+        "Volt",
+        "Vonino",
+        "Vontar",
+        "Vorago",
+        "Vorcom",
+        "Vorke",
+        "Vormor",
+        "Vortex",
+        "VORTEX (RO)",
+        "Voto",
+        "VOX Electronics",
+        "Voxtel",
+        "Voyo",
+        "Vsmart",
+        "Vsun",
+        "VUCATIMES",
+        "Vue Micro",
+        "Vulcan",
+        "VVETIME",
+        "Völfen",
+        "W&O",
+        "WAF",
+        "Wainyok",
+        "waipu.tv",
+        "Walker",
+        "Waltham",
+        "Walton",
+        "Waltter",
+        "WANSA",
+        "WE",
+        "We. by Loewe.",
+        "Webfleet",
+        "WeChip",
+        "Wecool",
+        "Weelikeit",
+        "Weiimi",
+        "Weimei",
+        "WellcoM",
+        "WELLINGTON",
+        "Western Digital",
+        "Weston",
+        "Westpoint",
+        "Wexler",
+        "Whoop",
+        "Wieppo",
+        "Wigor",
+        "Wiko",
+        "WildRed",
+        "Wileyfox",
+        "Winds",
+        "Wink",
+        "Winmax",
+        "Winnovo",
+        "Winstar",
+        "Wintouch",
+        "Wiseasy",
+        "WIWA",
+        "WizarPos",
+        "Wizz",
+        "Wolder",
+        "Wolfgang",
+        "Wolki",
+        "WONDER",
+        "Wonu",
+        "Woo",
+        "Woxter",
+        "WOZIFAN",
+        "WS",
+        "X-AGE",
+        "X-BO",
+        "X-Mobile",
+        "X-TIGI",
+        "X-View",
+        "X.Vision",
+        "X88",
+        "X96",
+        "X96Q",
+        "XB",
+        "Xcell",
+        "XCOM",
+        "Xcruiser",
+        "XElectron",
+        "XGEM",
+        "XGIMI",
+        "Xgody",
+        "Xiaodu",
+        "Xiaolajiao",
+        "Xiaomi",
+        "Xion",
+        "Xolo",
+        "Xoro",
+        "XPPen",
+        "XREAL",
+        "Xshitou",
+        "Xsmart",
+        "Xtouch",
+        "Xtratech",
+        "Xwave",
+        "XY Auto",
+        "Yandex",
+        "Yarvik",
+        "YASIN",
+        "YELLYOUTH",
+        "YEPEN",
+        "Yes",
+        "Yestel",
+        "Yezz",
+        "YIKEMI",
+        "Yoka TV",
+        "Yooz",
+        "Yota",
+        "YOTOPT",
+        "Youin",
+        "Youwei",
+        "Ytone",
+        "Yu",
+        "YU Fly",
+        "Yuandao",
+        "YUHO",
+        "YUMKEM",
+        "YUNDOO",
+        "Yuno",
+        "YunSong",
+        "Yusun",
+        "Yxtel",
+        "Z-Kai",
+        "Zaith",
+        "ZALA",
+        "Zamolxe",
+        "Zatec",
+        "Zealot",
+        "Zeblaze",
+        "Zebra",
+        "Zeeker",
+        "Zeemi",
+        "Zen",
+        "Zenek",
+        "Zentality",
+        "Zfiner",
+        "ZH&K",
+        "Zhongyu Display",
+        "Zidoo",
+        "ZIFFLER",
+        "ZIFRO",
+        "Zigo",
+        "ZIK",
+        "Zimmer",
+        "Zinox",
+        "ZIOVO",
+        "Ziox",
+        "Zonda",
+        "Zonko",
+        "Zoom",
+        "ZoomSmart",
+        "Zopo",
+        "ZTE",
+        "Zuum",
+        "Zync",
+        "ZYQ",
+        "Zyrex",
+        "ZZB",
+        "öwn",
+    ]
+    .into_iter()
+    .map(|name| name.to_owned())
+    .collect::<Vec<String>>()
+}
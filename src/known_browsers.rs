@@ -7,14 +7,15 @@ use serde::Serialize;
 // to make use of it while having any hope of keeping it up to date with changes upstream.
 
 pub struct AvailableBrowsers {
-    // TODO this may not ever be needed.
-    //    browsers_by_short: HashMap<String, AvailableBrowser>,
+    browsers_by_short: HashMap<String, AvailableBrowser>,
     browsers_by_name: HashMap<String, AvailableBrowser>,
 }
 
 #[derive(Clone, Debug, Serialize)]
 pub struct AvailableBrowser {
     pub name: String,
+    /// Upstream's short code, eg. `CH` for Chrome.
+    pub short_name: String,
     pub family: Option<String>,
     #[serde(skip)]
     pub(crate) mobile_only: bool,
@@ -41,9 +42,10 @@ impl Default for AvailableBrowsers {
                 let mobile_only = mobile_only_browsers.contains(&short);
                 let family = browser_families.get(&short).cloned();
                 (
-                    short,
+                    short.clone(),
                     AvailableBrowser {
                         name: browser,
+                        short_name: short,
                         mobile_only,
                         family,
                     },
@@ -62,13 +64,27 @@ impl Default for AvailableBrowsers {
             .collect::<HashMap<String, AvailableBrowser>>();
 
         Self {
-            //browsers_by_short,
+            browsers_by_short,
             browsers_by_name,
         }
     }
 }
 
 impl AvailableBrowsers {
+    /// The browser with upstream's short code `short`, eg. `CH`.
+    pub fn by_short(&self, short: &str) -> Option<&AvailableBrowser> {
+        self.browsers_by_short.get(short)
+    }
+
+    /// The browser named `name`, ignoring case like upstream does, see
+    /// [`Self::search_by_name`] for a more forgiving search.
+    pub fn by_name(&self, name: &str) -> Option<&AvailableBrowser> {
+        let name = name.to_lowercase();
+        self.browsers_by_name
+            .get(&name.replace(' ', ""))
+            .filter(|browser| browser.name.to_lowercase() == name)
+    }
+
     pub fn search_by_name(&self, name: &str) -> Option<&AvailableBrowser> {
         let name = name.to_lowercase().replace(' ', "");
         self.browsers_by_name
@@ -2,14 +2,15 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 
 pub struct AvailableOSs {
-    // TODO this may not ever be needed.
-    #[allow(dead_code)]
+    oss_by_short: HashMap<String, AvailableOS>,
     oss_by_name: HashMap<String, AvailableOS>,
 }
 
 #[derive(Clone, Debug)]
 pub struct AvailableOS {
     pub name: String,
+    /// Upstream's short code, eg. `AND` for Android.
+    pub short_name: String,
     pub desktop: bool,
     pub family: Option<String>,
 }
@@ -41,9 +42,10 @@ impl Default for AvailableOSs {
                 };
 
                 (
-                    short,
+                    short.clone(),
                     AvailableOS {
                         name: os,
+                        short_name: short,
                         desktop,
                         family,
                     },
@@ -56,11 +58,28 @@ impl Default for AvailableOSs {
             .map(|os| (os.name.replace(' ', "").to_lowercase(), os.clone()))
             .collect::<HashMap<String, AvailableOS>>();
 
-        Self { oss_by_name }
+        Self {
+            oss_by_short,
+            oss_by_name,
+        }
     }
 }
 
 impl AvailableOSs {
+    /// The os with upstream's short code `short`, eg. `AND`.
+    pub fn by_short(&self, short: &str) -> Option<&AvailableOS> {
+        self.oss_by_short.get(short)
+    }
+
+    /// The os named `name`, ignoring case like upstream does, see
+    /// [`Self::search_by_name`] for a more forgiving search.
+    pub fn by_name(&self, name: &str) -> Option<&AvailableOS> {
+        let name = name.to_lowercase();
+        self.oss_by_name
+            .get(&name.replace(' ', ""))
+            .filter(|os| os.name.to_lowercase() == name)
+    }
+
     pub fn search_by_name(&self, name: &str) -> Option<&AvailableOS> {
         let name = name.to_lowercase().replace(' ', "");
         self.oss_by_name.get(&name)
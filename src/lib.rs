@@ -1,10 +1,21 @@
+pub mod accuracy;
+pub mod automation;
+pub mod batch;
+pub mod cache;
 pub mod client_hints;
+pub mod consistency;
 pub mod device_detector;
+pub mod error;
 #[cfg(feature = "build-binary")]
 pub mod http;
+pub mod known_brands;
 pub mod known_browsers;
 pub mod known_oss;
 pub mod parsers;
+pub mod pipeline;
+pub mod rule_database;
+pub mod trace;
+pub mod verify;
 
 #[cfg(feature = "ffi")]
 pub mod ffi;
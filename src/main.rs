@@ -9,9 +9,11 @@ use std::process::ExitCode;
 
 use clap::{ArgAction, Parser};
 use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
 
 use rust_device_detector::device_detector::DeviceDetector;
 use rust_device_detector::http::server;
+use rust_device_detector::rule_database::RuleDatabase;
 
 #[derive(Parser, Debug)]
 /// A commandline user agent detection tool
@@ -48,6 +50,36 @@ struct Args {
     #[arg(short = 'p', long = "port", default_value = "8080")]
     port: u16,
 
+    /// Load the rule database from this directory instead of the one compiled
+    /// into the binary.
+    ///
+    /// The directory must be laid out like upstream's `regexes/` directory, so
+    /// that newer upstream rules can be used without rebuilding. In server mode
+    /// the directory is read again on SIGHUP or `POST /admin/reload`.
+    #[arg(short = 'r', long = "regexes", value_name = "DIR")]
+    regexes: Option<String>,
+
+    /// Check the rules in PATH before those of FILE, eg. `bots.yml=partner_bots.yml`.
+    ///
+    /// PATH holds extra rules in the same schema as FILE, which is relative to the
+    /// `regexes/` directory, such as `bots.yml`, `client/mobile_apps.yml` or
+    /// `device/mobiles.yml`. May be given more than once, overlays of the same file
+    /// being checked in the order given. In server mode overlays are read again
+    /// along with the rules.
+    #[arg(long = "overlay", action = ArgAction::Append, value_name = "FILE=PATH")]
+    overlays: Option<Vec<String>>,
+
+    /// Print the rules whose regexes need the backtracking engine, then exit.
+    ///
+    /// Every other rule runs in linear time. Backtracking rules may give up on
+    /// pathological user agents, which then fail to parse with a regex limit error.
+    #[arg(long = "backtracking-report")]
+    backtracking_report: bool,
+
+    /// Print the brands the device rules name that aren't upstream's, then exit.
+    #[arg(long = "brand-report")]
+    brand_report: bool,
+
     #[cfg(feature = "cache")]
     /// If set, how many entries to cache in an lru cache.
     ///
@@ -60,7 +92,12 @@ struct Args {
     /// When in cli mode (the default) this is the user agent to parse.
     ///
     /// Always remember escape shell arguments!
-    #[arg(required_unless_present_any(["interactive", "server"]))]
+    #[arg(required_unless_present_any([
+        "interactive",
+        "server",
+        "backtracking_report",
+        "brand_report"
+    ]))]
     useragent: Option<String>,
 
     // TODO we need to be able to just pass in a big block of headers as a single parameter
@@ -80,6 +117,14 @@ struct Args {
     /// php version of the detector.
     #[arg(long = "gen-test-case", default_value = "false")]
     gen_test_case: bool,
+
+    /// Print how the detection came about instead of the normal output.
+    ///
+    /// Along with the detection, lists which rule file and rule matched for the
+    /// bot, os, client and device, and which fields client hints or the
+    /// heuristics applied afterwards changed.
+    #[arg(long = "explain", default_value = "false")]
+    explain: bool,
 }
 
 // use stats_alloc::{Region, StatsAlloc, INSTRUMENTED_SYSTEM};
@@ -96,15 +141,75 @@ async fn main() -> Result<(), ExitCode> {
     // let reg = stats_alloc::Region::new(&INSTRUMENTED_SYSTEM);
 
     let args = Args::parse();
-    #[cfg(not(feature = "cache"))]
-    let detector = DeviceDetector::new();
+
+    let overlays = args.overlays.clone().unwrap_or_default();
+    let rules = if args.regexes.is_some() || !overlays.is_empty() {
+        let mut rules = RuleDatabase::builder();
+        if let Some(dir) = &args.regexes {
+            rules = rules.dir(dir);
+        }
+        for overlay in overlays {
+            let Some((file, path)) = overlay.split_once('=') else {
+                eprintln!("Overlays are given as FILE=PATH, not {}", overlay);
+                std::process::exit(1);
+            };
+            rules = rules.overlay_file(file, path);
+        }
+        Some(rules)
+    } else {
+        None
+    };
+
+    let detector = match &rules {
+        Some(rules) => match rules.build() {
+            Ok(database) => DeviceDetector::with_database(Arc::new(database)),
+            Err(err) => {
+                eprintln!("Unable to load rules: {}", err);
+                std::process::exit(1);
+            }
+        },
+        None => DeviceDetector::new(),
+    };
+
+    if args.backtracking_report {
+        let backtracking = detector
+            .database()
+            .backtracking_rules()
+            .unwrap_or_else(|err| {
+                eprintln!("Invalid rule database: {:?}", err);
+                std::process::exit(1);
+            });
+        for rule in backtracking {
+            match rule.overlay {
+                Some(overlay) => println!(
+                    "{} overlay {} rule {}: {}",
+                    rule.file, overlay, rule.rule, rule.pattern
+                ),
+                None => println!("{} rule {}: {}", rule.file, rule.rule, rule.pattern),
+            }
+        }
+        return Ok(());
+    }
+
+    if args.brand_report {
+        for brand in detector.database().unknown_brands() {
+            match brand.overlay {
+                Some(overlay) => println!(
+                    "{} overlay {} rule {}: {}",
+                    brand.file, overlay, brand.rule, brand.brand
+                ),
+                None => println!("{} rule {}: {}", brand.file, brand.rule, brand.brand),
+            }
+        }
+        return Ok(());
+    }
 
     #[cfg(feature = "cache")]
     let detector = if let Some(entries) = args.cache {
         eprintln!("Cache enabled ({} entries)", entries);
-        DeviceDetector::new_with_cache(entries)
+        detector.with_cache(entries)
     } else {
-        DeviceDetector::new()
+        detector
     };
 
     if args.interactive {
@@ -113,9 +218,24 @@ async fn main() -> Result<(), ExitCode> {
         while std::io::stdin().read_line(&mut ua).unwrap() > 0 {
             let headers = None;
 
-            let detection = detector
-                .parse(ua.trim_end(), headers)
-                .unwrap_or_else(|_| panic!("parse failed for {}", &ua));
+            if args.explain {
+                match detector.explain(ua.trim_end(), headers) {
+                    Ok(explanation) => println!("{}", explanation.to_value()),
+                    Err(err) => eprintln!("parse failed {} for '{}'", err, ua.trim_end()),
+                }
+
+                ua.clear();
+                continue;
+            }
+
+            let detection = match detector.parse(ua.trim_end(), headers) {
+                Ok(detection) => detection,
+                Err(err) => {
+                    eprintln!("parse failed {} for '{}'", err, ua.trim_end());
+                    ua.clear();
+                    continue;
+                }
+            };
 
             if args.gen_test_case {
                 println!("{}", detection.to_test_case(&ua));
@@ -131,7 +251,7 @@ async fn main() -> Result<(), ExitCode> {
         let ip: IpAddr = args.ip.parse().expect("valid ip address (ipv4 or ipv6)");
         let sock = SocketAddr::new(ip, args.port);
 
-        server(sock, detector).await;
+        server(sock, detector, rules).await;
     } else {
         match args.useragent {
             None => {
@@ -151,10 +271,10 @@ async fn main() -> Result<(), ExitCode> {
                                 // TODO FIXME handle all of these panics more cleanly.
                                 let name = split
                                     .next()
-                                    .expect(&format!("Unable to parse header {}", x));
+                                    .unwrap_or_else(|| panic!("Unable to parse header {}", x));
                                 let val = split
                                     .next()
-                                    .expect(&format!("Unable to parse header {}", x));
+                                    .unwrap_or_else(|| panic!("Unable to parse header {}", x));
                                 Some((name.to_owned(), val.trim_start().to_owned()))
                             })
                             .collect()
@@ -163,9 +283,19 @@ async fn main() -> Result<(), ExitCode> {
                     None => None,
                 };
 
-                let detection = detector
-                    .parse(&ua, headers)
-                    .unwrap_or_else(|err| panic!("parse failed {} for '{}'", err, &ua));
+                if args.explain {
+                    let explanation = detector.explain(&ua, headers).unwrap_or_else(|err| {
+                        eprintln!("parse failed {} for '{}'", err, &ua);
+                        std::process::exit(1);
+                    });
+                    println!("{}", explanation.to_value());
+                    return Ok(());
+                }
+
+                let detection = detector.parse(&ua, headers).unwrap_or_else(|err| {
+                    eprintln!("parse failed {} for '{}'", err, &ua);
+                    std::process::exit(1);
+                });
 
                 if args.gen_test_case {
                     println!("{}", detection.to_test_case(&ua));
@@ -1,27 +1,128 @@
-use anyhow::Result;
+use crate::error::Result;
 
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 
-use once_cell::sync::Lazy;
-use serde::Serialize;
+use serde::{Serialize, Serializer};
 
-use crate::parsers::utils::{expand, lazy_user_agent_match, LazyRegex};
+use std::collections::HashSet;
+
+use once_cell::sync::OnceCell;
 
-static BOT_LIST: Lazy<BotList> = Lazy::new(|| {
-    let contents = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/regexes/bots.yml"));
-    BotList::from_file(contents).unwrap_or_else(|_| panic!("loading bots.yml"))
-});
+use crate::parsers::feeds;
+use crate::parsers::prefilter::LiteralPrefilter;
+use crate::parsers::utils::{expand, lazy_user_agent_match, LazyRegex};
+use crate::rule_database::{RuleDatabase, RuleList};
+use crate::trace::{self, Part};
+use crate::verify::Verification;
 
-pub fn lookup_bot(ua: &str) -> Result<Option<Bot>> {
-    BOT_LIST.lookup(ua)
+pub fn lookup_bot(db: &RuleDatabase, ua: &str) -> Result<Option<Bot>> {
+    db.bots.lookup(ua)
 }
 
 #[derive(Clone, Debug, Serialize)]
 pub struct Bot {
     pub name: String,
-    pub category: Option<String>,
+    pub category: Option<BotCategory>,
     pub url: Option<String>,
     pub producer: Option<BotProducer>,
+    /// The subscriber count a feed fetcher reports, eg. `45` for `45 subscribers`.
+    pub subscribers: Option<u64>,
+    /// The id of the feed being fetched, for feed fetchers that report one.
+    pub feed_id: Option<String>,
+    /// Whether the client is known to be the bot, see
+    /// [`DeviceDetector::parse_with_ip`](crate::device_detector::DeviceDetector::parse_with_ip).
+    pub verification: Verification,
+}
+
+/// What a bot does, as the `category` of its rule names it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BotCategory {
+    AiAgent,
+    AiAssistant,
+    AiDataScraper,
+    AiSearchCrawler,
+    Benchmark,
+    Crawler,
+    FeedFetcher,
+    FeedParser,
+    FeedReader,
+    NetworkMonitor,
+    ReadItLaterService,
+    SearchBot,
+    SearchTools,
+    SecurityChecker,
+    SecuritySearchBot,
+    ServiceAgent,
+    ServiceBot,
+    SiteMonitor,
+    SocialMediaAgent,
+    Validator,
+    /// A category none of the above are, eg. one added by newer rules.
+    Other(String),
+}
+
+impl BotCategory {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::AiAgent => "AI Agent",
+            Self::AiAssistant => "AI Assistant",
+            Self::AiDataScraper => "AI Data Scraper",
+            Self::AiSearchCrawler => "AI Search Crawler",
+            Self::Benchmark => "Benchmark",
+            Self::Crawler => "Crawler",
+            Self::FeedFetcher => "Feed Fetcher",
+            Self::FeedParser => "Feed Parser",
+            Self::FeedReader => "Feed Reader",
+            Self::NetworkMonitor => "Network Monitor",
+            Self::ReadItLaterService => "Read-it-later Service",
+            Self::SearchBot => "Search bot",
+            Self::SearchTools => "Search tools",
+            Self::SecurityChecker => "Security Checker",
+            Self::SecuritySearchBot => "Security search bot",
+            Self::ServiceAgent => "Service Agent",
+            Self::ServiceBot => "Service bot",
+            Self::SiteMonitor => "Site Monitor",
+            Self::SocialMediaAgent => "Social Media Agent",
+            Self::Validator => "Validator",
+            Self::Other(category) => category,
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(category: &str) -> BotCategory {
+        match category {
+            "AI Agent" => Self::AiAgent,
+            "AI Assistant" => Self::AiAssistant,
+            "AI Data Scraper" => Self::AiDataScraper,
+            "AI Search Crawler" => Self::AiSearchCrawler,
+            "Benchmark" => Self::Benchmark,
+            "Crawler" => Self::Crawler,
+            "Feed Fetcher" => Self::FeedFetcher,
+            "Feed Parser" => Self::FeedParser,
+            "Feed Reader" => Self::FeedReader,
+            "Network Monitor" => Self::NetworkMonitor,
+            "Read-it-later Service" => Self::ReadItLaterService,
+            "Search bot" => Self::SearchBot,
+            "Search tools" => Self::SearchTools,
+            "Security Checker" => Self::SecurityChecker,
+            "Security search bot" => Self::SecuritySearchBot,
+            "Service Agent" => Self::ServiceAgent,
+            "Service bot" => Self::ServiceBot,
+            "Site Monitor" => Self::SiteMonitor,
+            "Social Media Agent" => Self::SocialMediaAgent,
+            "Validator" => Self::Validator,
+            other => Self::Other(other.to_owned()),
+        }
+    }
+}
+
+impl Serialize for BotCategory {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -35,8 +136,10 @@ struct BotEntry {
     regex: LazyRegex,
     name: String,
     url: Option<String>,
-    category: Option<String>,
+    category: Option<BotCategory>,
     producer: Option<BotProducer>,
+    subscribers: Option<LazyRegex>,
+    feed_id: Option<LazyRegex>,
 }
 
 impl From<&BotEntry> for Bot {
@@ -46,6 +149,9 @@ impl From<&BotEntry> for Bot {
             category: entry.category.clone(),
             url: entry.url.clone(),
             producer: entry.producer.clone(),
+            subscribers: None,
+            feed_id: None,
+            verification: Verification::Unknown,
         }
     }
 }
@@ -53,10 +159,117 @@ impl From<&BotEntry> for Bot {
 #[derive(Debug)]
 pub struct BotList {
     bots: Vec<BotEntry>,
+    prefilter: OnceCell<LiteralPrefilter>,
 }
 
 impl BotList {
-    pub fn from_file(contents: &str) -> Result<BotList> {
+    /// Every regex of this list along with the index of its rule, in rule order.
+    pub(crate) fn regexes(&self) -> impl Iterator<Item = (usize, &LazyRegex)> {
+        self.bots.iter().enumerate().flat_map(|(rule, bot)| {
+            std::iter::once(&bot.regex)
+                .chain(bot.subscribers.as_ref())
+                .chain(bot.feed_id.as_ref())
+                .map(move |regex| (rule, regex))
+        })
+    }
+
+    /// Every bot the rules know of, once per name, in rule order. Some names are
+    /// templates filled in from the user agent, eg. `$1`.
+    pub fn all(&self) -> Vec<Bot> {
+        let mut seen = HashSet::new();
+        self.bots
+            .iter()
+            .filter(|bot| seen.insert(bot.name.as_str()))
+            .map(Bot::from)
+            .collect()
+    }
+
+    /// Every bot of `category`, see [`Self::all`].
+    pub fn by_category(&self, category: &BotCategory) -> Vec<Bot> {
+        self.all()
+            .into_iter()
+            .filter(|bot| bot.category.as_ref() == Some(category))
+            .collect()
+    }
+
+    /// Every bot made by `producer`, eg. `Google Inc.`, ignoring case.
+    pub fn by_producer(&self, producer: &str) -> Vec<Bot> {
+        let producer = producer.to_lowercase();
+        self.all()
+            .into_iter()
+            .filter(|bot| {
+                bot.producer
+                    .as_ref()
+                    .and_then(|x| x.name.as_deref())
+                    .is_some_and(|name| name.to_lowercase() == producer)
+            })
+            .collect()
+    }
+
+    /// Whether a bot is named exactly `name`, eg. to check a list of bots to allow.
+    pub fn contains(&self, name: &str) -> bool {
+        self.bots.iter().any(|bot| bot.name == name)
+    }
+
+    fn prefilter(&self) -> &LiteralPrefilter {
+        self.prefilter
+            .get_or_init(|| LiteralPrefilter::new(self.bots.iter().map(|bot| &bot.regex)))
+    }
+
+    pub(crate) fn lookup(&self, ua: &str) -> Result<Option<Bot>> {
+        let candidates = self.prefilter().candidates(ua);
+
+        for (rule, bot) in self.bots.iter().enumerate() {
+            if !candidates.contains(rule) {
+                continue;
+            }
+
+            if let Some(captures) = bot.regex.captures(ua)? {
+                trace::rule(Part::Bot, &bot.regex);
+                let mut bot_out: Bot = bot.into();
+                // Expand capture group references (e.g. $1) in name
+                if bot_out.name.contains('$') {
+                    let mut expanded = String::new();
+                    expand(&bot_out.name, &mut expanded, &captures);
+                    bot_out.name = expanded;
+                }
+
+                // only feed bots report subscribers and feed ids in the usual way, rules of
+                // others have to give their own regexes.
+                let defaults = matches!(
+                    bot.category,
+                    Some(
+                        BotCategory::FeedFetcher
+                            | BotCategory::FeedParser
+                            | BotCategory::FeedReader
+                    )
+                );
+                (bot_out.subscribers, bot_out.feed_id) =
+                    feeds::lookup(ua, bot.subscribers.as_ref(), bot.feed_id.as_ref(), defaults)?;
+                return Ok(Some(bot_out));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl RuleList for BotList {
+    fn len(&self) -> usize {
+        self.bots.len()
+    }
+
+    fn prepend(&mut self, overlay: Self) {
+        self.bots.splice(0..0, overlay.bots);
+        self.prefilter = OnceCell::new();
+    }
+}
+
+impl<'de> Deserialize<'de> for BotList {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
         #[derive(Debug, Deserialize)]
         struct YamlBotEntry {
             regex: String,
@@ -64,6 +277,10 @@ impl BotList {
             url: Option<String>,
             category: Option<String>,
             producer: Option<BotProducer>,
+            #[serde(default, deserialize_with = "feeds::de_regex")]
+            subscribers: Option<LazyRegex>,
+            #[serde(default, deserialize_with = "feeds::de_regex")]
+            feed_id: Option<LazyRegex>,
         }
 
         #[allow(clippy::from_over_into)]
@@ -82,8 +299,13 @@ impl BotList {
                     regex: lazy_user_agent_match(&self.regex),
                     name: self.name,
                     url: self.url,
-                    category: self.category,
+                    category: self
+                        .category
+                        .filter(|category| !category.is_empty())
+                        .map(|category| BotCategory::from_str(&category)),
                     producer: self.producer,
+                    subscribers: self.subscribers,
+                    feed_id: self.feed_id,
                 }
             }
         }
@@ -99,28 +321,12 @@ impl BotList {
             fn into(self) -> BotList {
                 BotList {
                     bots: self.bots.into_iter().map(|x| x.into()).collect(),
+                    prefilter: OnceCell::new(),
                 }
             }
         }
 
-        let res: YamlBotList = serde_yaml::from_str(contents)?;
+        let res = YamlBotList::deserialize(deserializer)?;
         Ok(res.into())
     }
-
-    fn lookup(&self, ua: &str) -> Result<Option<Bot>> {
-        for bot in self.bots.iter() {
-            if let Some(captures) = bot.regex.captures(ua)? {
-                let mut bot_out: Bot = bot.into();
-                // Expand capture group references (e.g. $1) in name
-                if bot_out.name.contains('$') {
-                    let mut expanded = String::new();
-                    expand(&bot_out.name, &mut expanded, &captures);
-                    bot_out.name = expanded;
-                }
-                return Ok(Some(bot_out));
-            }
-        }
-
-        Ok(None)
-    }
 }
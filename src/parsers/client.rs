@@ -17,13 +17,17 @@
 //
 //
 
-use anyhow::Result;
+use crate::error::Result;
 
 use serde::{Deserialize, Serialize};
 
 use serde::de::Deserializer;
 
+use once_cell::sync::OnceCell;
+
 use crate::known_browsers::AvailableBrowser;
+use crate::parsers::feeds;
+use crate::parsers::prefilter::LiteralPrefilter;
 use crate::parsers::utils::{lazy_user_agent_match, LazyRegex};
 
 pub mod browsers;
@@ -35,6 +39,11 @@ pub mod mobile_apps;
 pub mod pim;
 
 use crate::client_hints::ClientHint;
+use crate::device_detector::ParserFamily;
+use crate::parsers::oss::OS;
+use crate::pipeline::{self, ParseInput, Stage};
+use crate::rule_database::{RuleDatabase, RuleList};
+use crate::trace::{self, Part};
 
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, Eq, Serialize)]
@@ -73,34 +82,113 @@ pub struct Client {
     pub r#type: ClientType,
     pub engine: Option<String>,
     pub engine_version: Option<String>,
-
-    #[serde(skip)]
+    /// Upstream's short code, eg. `CH` for Chrome. Only browsers have one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub short_name: Option<String>,
+    /// The client's home page, as given by its rule. Feed readers and libraries
+    /// mostly have one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// A finer grained type given by the client's rule, eg. `Feed Reader App` as
+    /// older feed reader rules had it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtype: Option<String>,
+    /// The subscriber count a feed reader reports, eg. `45` for `45 subscribers`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscribers: Option<u64>,
+    /// The id of the feed being fetched, for feed readers that report one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feed_id: Option<String>,
+
+    // serialized as its family and mobile_only, see `Client::family`.
+    #[serde(flatten, serialize_with = "serialize_browser")]
     pub(crate) browser: Option<AvailableBrowser>,
 }
 
-pub fn lookup(ua: &str, client_hints: Option<&ClientHint>) -> Result<Option<Client>> {
-    if let Some(res) = feed_readers::lookup(ua)? {
-        return Ok(Some(res));
+fn serialize_browser<S>(
+    browser: &Option<AvailableBrowser>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    #[derive(Serialize)]
+    struct BrowserFields<'a> {
+        family: Option<&'a str>,
+        mobile_only: bool,
     }
 
-    if let Some(res) = mobile_apps::lookup(ua, client_hints)? {
-        return Ok(Some(res));
+    BrowserFields {
+        family: browser.as_ref().and_then(|browser| browser.family.as_deref()),
+        mobile_only: browser.as_ref().is_some_and(|browser| browser.mobile_only),
     }
+    .serialize(serializer)
+}
 
-    if let Some(res) = media_players::lookup(ua)? {
-        return Ok(Some(res));
+impl Client {
+    /// A client detected by a parser of your own, see
+    /// [`Parser`](crate::pipeline::Parser).
+    pub fn new(name: impl Into<String>, version: Option<String>, r#type: ClientType) -> Self {
+        Self {
+            name: name.into(),
+            version,
+            r#type,
+            engine: None,
+            engine_version: None,
+            short_name: None,
+            url: None,
+            subtype: None,
+            subscribers: None,
+            feed_id: None,
+            browser: None,
+        }
     }
 
-    if let Some(res) = pim::lookup(ua)? {
-        return Ok(Some(res));
+    /// The family of a browser, eg. `Chrome` for Chrome Mobile. Only browsers have one,
+    /// and not every browser.
+    pub fn family(&self) -> Option<&str> {
+        self.browser.as_ref().and_then(|browser| browser.family.as_deref())
     }
 
-    if let Some(res) = libraries::lookup(ua)? {
-        return Ok(Some(res));
+    /// Whether the browser is only ever found on mobile devices, eg. Opera Mobile.
+    pub fn mobile_only(&self) -> bool {
+        self.browser.as_ref().is_some_and(|browser| browser.mobile_only)
     }
+}
 
-    if let Some(res) = browsers::lookup(ua, client_hints)? {
-        return Ok(Some(res));
+pub(crate) fn lookup(
+    db: &RuleDatabase,
+    ua: &str,
+    client_hints: Option<&ClientHint>,
+    os: Option<&OS>,
+    parsers: &[Stage<Client>],
+    enabled: impl Fn(ParserFamily) -> bool,
+) -> Result<Option<Client>> {
+    for parser in parsers {
+        let res = match parser {
+            Stage::Builtin(family) if !enabled(*family) => None,
+            Stage::Builtin(ParserFamily::FeedReader) => feed_readers::lookup(db, ua)?,
+            Stage::Builtin(ParserFamily::MobileApp) => mobile_apps::lookup(db, ua, client_hints)?,
+            Stage::Builtin(ParserFamily::MediaPlayer) => media_players::lookup(db, ua)?,
+            Stage::Builtin(ParserFamily::Pim) => pim::lookup(db, ua)?,
+            Stage::Builtin(ParserFamily::Library) => libraries::lookup(db, ua)?,
+            Stage::Builtin(ParserFamily::Browser) => browsers::lookup(db, ua, client_hints)?,
+            // device parsers, which never make it into this chain.
+            Stage::Builtin(_) => None,
+            Stage::Custom(parser) => {
+                let input = ParseInput {
+                    ua,
+                    client_hints,
+                    os,
+                    client: None,
+                };
+                pipeline::run(Part::Client, &**parser, &input)?
+            }
+        };
+
+        if res.is_some() {
+            return Ok(res);
+        }
     }
 
     Ok(None)
@@ -112,17 +200,61 @@ pub struct ClientEntry {
     #[serde(deserialize_with = "de_regex")]
     regex: LazyRegex,
     version: String,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default, rename = "type")]
+    subtype: Option<String>,
+    #[serde(default, deserialize_with = "feeds::de_regex")]
+    subscribers: Option<LazyRegex>,
+    #[serde(default, deserialize_with = "feeds::de_regex")]
+    feed_id: Option<LazyRegex>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
 pub struct ClientList {
     clients: Vec<ClientEntry>,
+    #[serde(skip)]
+    prefilter: OnceCell<LiteralPrefilter>,
+}
+
+impl RuleList for ClientList {
+    fn len(&self) -> usize {
+        self.clients.len()
+    }
+
+    fn prepend(&mut self, overlay: Self) {
+        self.clients.splice(0..0, overlay.clients);
+        self.prefilter = OnceCell::new();
+    }
 }
 
 impl ClientList {
+    /// Every regex of this list along with the index of its rule, in rule order.
+    pub(crate) fn regexes(&self) -> impl Iterator<Item = (usize, &LazyRegex)> {
+        self.clients.iter().enumerate().flat_map(|(rule, client)| {
+            std::iter::once(&client.regex)
+                .chain(client.subscribers.as_ref())
+                .chain(client.feed_id.as_ref())
+                .map(move |regex| (rule, regex))
+        })
+    }
+
+    fn prefilter(&self) -> &LiteralPrefilter {
+        self.prefilter
+            .get_or_init(|| LiteralPrefilter::new(self.clients.iter().map(|client| &client.regex)))
+    }
+
     pub fn lookup(&self, ua: &str, r#type: ClientType) -> Result<Option<Client>> {
-        for client in self.clients.iter() {
+        let candidates = self.prefilter().candidates(ua);
+
+        for (rule, client) in self.clients.iter().enumerate() {
+            if !candidates.contains(rule) {
+                continue;
+            }
+
             if client.regex.is_match(ua)? {
+                trace::rule(Part::Client, &client.regex);
                 let mut version = "".to_owned();
                 let mut name = "".to_owned();
                 let caps = client.regex.captures(ua)?.expect("valid_regex");
@@ -131,8 +263,8 @@ impl ClientList {
                 caps.expand(&client.version, &mut version);
 
                 // TODO I don't know if this is needed, but here it is.
-                let version = if version.ends_with(&['.', ' ']) {
-                    version.trim_end_matches(&['.', ' ']).to_owned()
+                let version = if version.ends_with(['.', ' ']) {
+                    version.trim_end_matches(['.', ' ']).to_owned()
                 } else {
                     version
                 };
@@ -145,6 +277,13 @@ impl ClientList {
 
                 caps.expand(&client.name, &mut name);
 
+                let (subscribers, feed_id) = feeds::lookup(
+                    ua,
+                    client.subscribers.as_ref(),
+                    client.feed_id.as_ref(),
+                    r#type == ClientType::FeedReader,
+                )?;
+
                 return Ok(Some(Client {
                     name,
                     version,
@@ -152,32 +291,17 @@ impl ClientList {
                     browser: None,
                     engine: None,
                     engine_version: None,
+                    short_name: None,
+                    url: client.url.clone().filter(|url| !url.is_empty()),
+                    subtype: client.subtype.clone().filter(|subtype| !subtype.is_empty()),
+                    subscribers,
+                    feed_id,
                 }));
             }
         }
 
         Ok(None)
     }
-
-    pub fn from_file(contents: &str) -> Result<ClientList> {
-        #[derive(Debug, Deserialize)]
-        #[serde(transparent)]
-        pub struct YamlClientList {
-            clients: Vec<ClientEntry>,
-        }
-
-        #[allow(clippy::from_over_into)]
-        impl Into<ClientList> for YamlClientList {
-            fn into(self) -> ClientList {
-                ClientList {
-                    clients: self.clients,
-                }
-            }
-        }
-
-        let res: YamlClientList = serde_yaml::from_str(contents)?;
-        Ok(res.into())
-    }
 }
 
 // The php version uses this to try and speed things up, match all
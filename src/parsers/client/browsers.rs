@@ -1,6 +1,6 @@
-use anyhow::Result;
+use crate::error::{DetectError, Result};
 
-use fancy_regex::Regex;
+use crate::parsers::utils::SafeRegex as Regex;
 
 use serde::Deserialize;
 
@@ -14,14 +14,17 @@ use fallible_iterator::{convert, FallibleIterator};
 
 use super::{Client, ClientType};
 use crate::client_hints::{ClientHint, ClientHintMapping};
-use crate::known_browsers::AvailableBrowsers;
+use crate::known_browsers::{AvailableBrowser, AvailableBrowsers};
 
+use crate::parsers::prefilter::LiteralPrefilter;
 use crate::parsers::utils::LazyRegex;
+use crate::rule_database::{RuleDatabase, RuleList};
+use crate::trace::{self, Part, Reason};
 
 pub mod engines;
 use engines::detect_engine_version;
 
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 
 // Helper function to extract version from user agent for app-based browsers
 fn extract_version_from_ua(ua: &str, app_hint: &str) -> Result<Option<String>> {
@@ -54,14 +57,6 @@ const BROWSERS_USING_UA_VERSION_FINAL: &[&str] = &[
     "Aloha Browser", "JioSphere", "mCent", "Opera", "Opera Mini", "Opera Mobile"
 ];
 
-static CLIENT_LIST: Lazy<BrowserClientList> = Lazy::new(|| {
-    let contents = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/regexes/client/browsers.yml"
-    ));
-    BrowserClientList::from_file(contents).expect("loading browsers.yml")
-});
-
 static CLIENT_HINT_MAPPING: Lazy<ClientHintMapping> = Lazy::new(|| {
     ClientHintMapping::new(vec![
         ("Chrome".to_owned(), vec!["Google Chrome".to_owned()]),
@@ -104,8 +99,19 @@ static CLIENT_HINT_MAPPING: Lazy<ClientHintMapping> = Lazy::new(|| {
 
 static AVAILABLE_BROWSERS: Lazy<AvailableBrowsers> = Lazy::new(AvailableBrowsers::default);
 
-pub fn lookup(ua: &str, client_hints: Option<&ClientHint>) -> Result<Option<Client>> {
-    let client_from_ua: Option<Client> = CLIENT_LIST.lookup(ua)?;
+/// The browser a `Sec-CH-UA` brand stands for, eg. Chrome for `Google Chrome`.
+pub(crate) fn browser_from_hint(brand: &str) -> Option<&'static AvailableBrowser> {
+    let brand = CLIENT_HINT_MAPPING.apply(brand).ok()?;
+    AVAILABLE_BROWSERS.search_by_name(brand.trim())
+}
+
+pub fn lookup(
+    db: &RuleDatabase,
+    ua: &str,
+    client_hints: Option<&ClientHint>,
+) -> Result<Option<Client>> {
+    let client_from_ua: Option<Client> = db.browsers.lookup(db, ua)?;
+    let before = trace::snapshot(client_from_ua.as_ref());
 
     let mut client_from_hints = if let Some(client_hints) = client_hints {
         // Deduplicate brands like PHP's array_combine: last occurrence of a brand wins.
@@ -114,7 +120,7 @@ pub fn lookup(ua: &str, client_hints: Option<&ClientHint>) -> Result<Option<Clie
         for (brand, version) in &client_hints.full_version_list {
             brand_map.insert(brand.clone(), version.as_str());
         }
-        let client_hints_iter = convert(client_hints.full_version_list.iter().map(anyhow::Ok));
+        let client_hints_iter = convert(client_hints.full_version_list.iter().map(Ok::<_, DetectError>));
         let mut possible_results: Vec<_> = client_hints_iter
             .filter_map(|i| {
                 // Skip if this brand appeared later with a different version (use last occurrence)
@@ -183,6 +189,11 @@ pub fn lookup(ua: &str, client_hints: Option<&ClientHint>) -> Result<Option<Clie
                 r#type: ClientType::Browser,
                 engine,
                 engine_version: engine_version.clone(),
+                short_name: None,
+                url: None,
+                subtype: None,
+                subscribers: None,
+                feed_id: None,
                 browser: Some(brand_result.to_owned()),
             };
             Some(res)
@@ -295,12 +306,10 @@ pub fn lookup(ua: &str, client_hints: Option<&ClientHint>) -> Result<Option<Clie
                         let hints_version = client_from_hints.version.as_deref()
                             .or_else(|| client_hints.and_then(|h| h.ua_full_version.as_deref()));
                         match (hints_version, client.engine_version.as_deref()) {
-                            (Some(hv), Some(uv)) => {
-                                if version_compare::compare(hv, uv) == Ok(version_compare::Cmp::Gt) {
-                                    client_from_hints.engine_version = Some(hv.to_owned());
-                                } else {
-                                    client_from_hints.engine_version = client.engine_version.clone();
-                                }
+                            (Some(hv), Some(uv))
+                                if version_compare::compare(hv, uv) == Ok(version_compare::Cmp::Gt) =>
+                            {
+                                client_from_hints.engine_version = Some(hv.to_owned());
                             }
                             _ => {
                                 client_from_hints.engine_version = client.engine_version.clone();
@@ -327,12 +336,10 @@ pub fn lookup(ua: &str, client_hints: Option<&ClientHint>) -> Result<Option<Clie
                     let hints_version = client_from_hints.version.as_deref()
                         .or_else(|| client_hints.and_then(|h| h.ua_full_version.as_deref()));
                     match (hints_version, client.engine_version.as_deref()) {
-                        (Some(hv), Some(uv)) => {
-                            if version_compare::compare(hv, uv) == Ok(version_compare::Cmp::Gt) {
-                                client_from_hints.engine_version = Some(hv.to_owned());
-                            } else {
-                                client_from_hints.engine_version = client.engine_version.clone();
-                            }
+                        (Some(hv), Some(uv))
+                            if version_compare::compare(hv, uv) == Ok(version_compare::Cmp::Gt) =>
+                        {
+                            client_from_hints.engine_version = Some(hv.to_owned());
                         }
                         _ => {
                             client_from_hints.engine_version = client.engine_version.clone();
@@ -360,15 +367,18 @@ pub fn lookup(ua: &str, client_hints: Option<&ClientHint>) -> Result<Option<Clie
 
         // Additional browsers that need user agent version (handled after name resolution)
         if let Some(client) = &client_from_ua {
-            if !client.version.as_ref().unwrap_or(&String::new()).is_empty() {
-                if BROWSERS_USING_UA_VERSION_FINAL.contains(&client_from_hints.name.as_str()) {
-                    client_from_hints.version = client.version.clone();
-                }
+            if !client.version.as_ref().unwrap_or(&String::new()).is_empty()
+                && BROWSERS_USING_UA_VERSION_FINAL.contains(&client_from_hints.name.as_str())
+            {
+                client_from_hints.version = client.version.clone();
             }
         }
     };
 
     let mut res = client_from_hints.or(client_from_ua);
+    trace::changes(before, res.as_ref(), Reason::ClientHints);
+
+    let before = trace::snapshot(res.as_ref());
 
     // Special handling for Opera Mobile with WebView
     // If Chrome WebView is detected but the UA contains OPR/, it's actually Opera Mobile
@@ -407,7 +417,7 @@ pub fn lookup(ua: &str, client_hints: Option<&ClientHint>) -> Result<Option<Clie
     if let Some(client) = res.as_mut() {
         if let Some(client_hints) = client_hints {
             if let Some(app_hint) = &client_hints.app {
-                if let Some(app_name) = super::hints::browsers::get_hint(app_hint)? {
+                if let Some(app_name) = super::hints::browsers::get_hint(db, app_hint)? {
                     if client.name != app_name {
                         client.name = app_name.to_owned();
                         
@@ -492,7 +502,12 @@ pub fn lookup(ua: &str, client_hints: Option<&ClientHint>) -> Result<Option<Clie
                 client.engine_version = None;
             }
         }
+
+        client.short_name = AVAILABLE_BROWSERS
+            .by_name(&client.name)
+            .map(|browser| browser.short_name.clone());
     }
+    trace::changes(before, res.as_ref(), Reason::PostProcessing);
 
     Ok(res)
 }
@@ -515,22 +530,52 @@ struct BrowserEngine {
 
 #[derive(Debug, Default, Deserialize)]
 #[serde(transparent)]
-struct BrowserClientList {
+pub(crate) struct BrowserClientList {
     clients: Vec<BrowserClientEntry>,
+    #[serde(skip)]
+    prefilter: OnceCell<LiteralPrefilter>,
+}
+
+impl RuleList for BrowserClientList {
+    fn len(&self) -> usize {
+        self.clients.len()
+    }
+
+    fn prepend(&mut self, overlay: Self) {
+        self.clients.splice(0..0, overlay.clients);
+        self.prefilter = OnceCell::new();
+    }
 }
 
 impl BrowserClientList {
-    pub fn lookup(&self, ua: &str) -> Result<Option<Client>> {
-        for entry in self.clients.iter() {
+    /// Every regex of this list along with the index of its rule, in rule order.
+    pub(crate) fn regexes(&self) -> impl Iterator<Item = (usize, &LazyRegex)> {
+        self.clients.iter().map(|client| &client.regex).enumerate()
+    }
+
+    fn prefilter(&self) -> &LiteralPrefilter {
+        self.prefilter
+            .get_or_init(|| LiteralPrefilter::new(self.clients.iter().map(|entry| &entry.regex)))
+    }
+
+    pub fn lookup(&self, db: &RuleDatabase, ua: &str) -> Result<Option<Client>> {
+        let candidates = self.prefilter().candidates(ua);
+
+        for (rule, entry) in self.clients.iter().enumerate() {
+            if !candidates.contains(rule) {
+                continue;
+            }
+
             if entry.regex.is_match(ua)? {
+                trace::rule(Part::Client, &entry.regex);
                 let mut name = "".to_owned();
                 let mut version = "".to_owned();
 
                 let caps = entry.regex.captures(ua)?.expect("valid_regex");
 
                 caps.expand(&entry.version, &mut version);
-                let version = if version.ends_with(&['.', ' ']) {
-                    version.trim_end_matches(&['.', ' ']).to_owned()
+                let version = if version.ends_with(['.', ' ']) {
+                    version.trim_end_matches(['.', ' ']).to_owned()
                 } else {
                     version
                 };
@@ -542,13 +587,13 @@ impl BrowserClientList {
                 let mut engine_version = None;
 
                 if let Some(entry_engine) = &entry.engine {
-                    if let Some(e) = Self::engine(ua, entry_engine, &version)? {
+                    if let Some(e) = Self::engine(db, ua, entry_engine, &version)? {
                         engine = Some(e);
                     }
                 }
 
                 if engine.is_none() {
-                    engine = self::engines::lookup(ua)?;
+                    engine = self::engines::lookup(db, ua)?;
                 }
 
                 if let Some(e) = &engine {
@@ -573,6 +618,11 @@ impl BrowserClientList {
                     r#type: ClientType::Browser,
                     engine,
                     engine_version,
+                    short_name: None,
+                    url: None,
+                    subtype: None,
+                    subscribers: None,
+                    feed_id: None,
                     browser,
                 }));
             }
@@ -628,7 +678,12 @@ impl BrowserClientList {
         Ok(None)
     }
 
-    fn engine(ua: &str, entry_engine: &BrowserEngine, version: &str) -> Result<Option<String>> {
+    fn engine(
+        db: &RuleDatabase,
+        ua: &str,
+        entry_engine: &BrowserEngine,
+        version: &str,
+    ) -> Result<Option<String>> {
         let mut engine = None;
         let mut engine_versions = entry_engine.versions.iter().collect::<Vec<_>>();
 
@@ -655,14 +710,9 @@ impl BrowserClientList {
         engine = engine.or_else(|| entry_engine.default.clone());
 
         if engine.is_none() || engine.as_ref().unwrap() == "" {
-            engine = self::engines::lookup(ua)?;
+            engine = self::engines::lookup(db, ua)?;
         }
 
         Ok(engine)
     }
-
-    pub fn from_file(contents: &str) -> Result<Self> {
-        let res = serde_yaml::from_str(contents)?;
-        Ok(res)
-    }
 }
@@ -1,18 +1,12 @@
-use anyhow::Result;
-use serde::Deserialize;
-use fancy_regex::Regex;
+use crate::error::Result;
+use serde::{Deserialize, Deserializer};
+use crate::parsers::utils::SafeRegex as Regex;
 
 use crate::parsers::utils::{lazy_user_agent_match, LazyRegex};
+use crate::rule_database::{RuleDatabase, RuleList};
+use crate::trace::{self, Part};
 use once_cell::sync::Lazy;
 
-static ENGINE_LIST: Lazy<BrowserEngineList> = Lazy::new(|| {
-    let contents = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/regexes/client/browser_engine.yml"
-    ));
-    BrowserEngineList::from_file(contents).expect("loading browser_engine.yml")
-});
-
 static AVAILABLE_ENGINES: Lazy<Vec<String>> = Lazy::new(|| {
     // hard coded list taken from matamoto device detector
     let engines = [
@@ -39,9 +33,9 @@ static AVAILABLE_ENGINES: Lazy<Vec<String>> = Lazy::new(|| {
     engines.into_iter().map(|x| x.to_owned()).collect()
 });
 
-pub fn lookup(name: &str) -> Result<Option<String>> {
+pub fn lookup(db: &RuleDatabase, name: &str) -> Result<Option<String>> {
     // println!("browser engine lookup {}", name);
-    let res = match ENGINE_LIST.lookup(name)? {
+    let res = match db.browser_engines.lookup(name)? {
         None => AVAILABLE_ENGINES
             .iter()
             .find(|engine| engine.to_lowercase() == name.to_lowercase())
@@ -51,7 +45,8 @@ pub fn lookup(name: &str) -> Result<Option<String>> {
     Ok(res)
 }
 
-struct BrowserEngineList {
+#[derive(Debug)]
+pub(crate) struct BrowserEngineList {
     list: Vec<BrowserEngine>,
 }
 
@@ -62,10 +57,16 @@ struct BrowserEngine {
 }
 
 impl BrowserEngineList {
+    /// Every regex of this list along with the index of its rule, in rule order.
+    pub(crate) fn regexes(&self) -> impl Iterator<Item = (usize, &LazyRegex)> {
+        self.list.iter().map(|engine| &engine.regex).enumerate()
+    }
+
     fn lookup(&self, ua: &str) -> Result<Option<String>> {
         for engine in &self.list {
             // println!("engine {:?}", engine);
             if engine.regex.is_match(ua)? {
+                trace::rule(Part::Engine, &engine.regex);
                 // println!("engine match {:?}", engine);
                 return Ok(Some(engine.name.clone()));
             }
@@ -73,7 +74,23 @@ impl BrowserEngineList {
 
         Ok(None)
     }
-    fn from_file(contents: &str) -> Result<Self> {
+}
+
+impl RuleList for BrowserEngineList {
+    fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    fn prepend(&mut self, overlay: Self) {
+        self.list.splice(0..0, overlay.list);
+    }
+}
+
+impl<'de> Deserialize<'de> for BrowserEngineList {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
         #[derive(Debug, Deserialize)]
         #[serde(transparent)]
         struct YamlBrowserEngineList {
@@ -106,7 +123,7 @@ impl BrowserEngineList {
             }
         }
 
-        let res: YamlBrowserEngineList = serde_yaml::from_str(contents)?;
+        let res = YamlBrowserEngineList::deserialize(deserializer)?;
         Ok(res.into())
     }
 }
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use crate::error::Result;
 use serde::Deserialize;
 use std::collections::HashMap;
 
@@ -12,11 +12,6 @@ pub struct HintList {
 }
 
 impl HintList {
-    pub fn from_file(contents: &str) -> Result<HintList> {
-        let hints: HintList = serde_yaml::from_str(contents)?;
-        Ok(hints)
-    }
-
     pub fn get_hint(&self, app: &str) -> Result<Option<&str>> {
         let res = self.hints.get(app);
         Ok(res.map(|s| s.as_ref()))
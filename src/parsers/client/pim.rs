@@ -1,16 +1,8 @@
-use anyhow::Result;
+use crate::error::Result;
 
-use super::{Client, ClientList};
-use once_cell::sync::Lazy;
+use super::Client;
+use crate::rule_database::RuleDatabase;
 
-static CLIENT_LIST: Lazy<ClientList> = Lazy::new(|| {
-    let contents = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/regexes/client/pim.yml"
-    ));
-    ClientList::from_file(contents).expect("loading pim.yml")
-});
-
-pub fn lookup(ua: &str) -> Result<Option<Client>> {
-    CLIENT_LIST.lookup(ua, super::ClientType::Pim)
+pub fn lookup(db: &RuleDatabase, ua: &str) -> Result<Option<Client>> {
+    db.pims.lookup(ua, super::ClientType::Pim)
 }
@@ -1,10 +1,10 @@
-use anyhow::Result;
+use crate::error::{DetectError, Result};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use serde_yaml::Value;
 
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 
 use version_compare::{self, Version};
 
@@ -12,9 +12,16 @@ use super::vendor_fragments;
 
 use std::borrow::Cow;
 
+use crate::accuracy;
 use crate::client_hints::ClientHint;
+use crate::device_detector::ParserFamily;
+use crate::known_brands::AvailableBrands;
 use crate::parsers::client::{Client, ClientType};
 use crate::parsers::oss::OS;
+use crate::parsers::prefilter::LiteralPrefilter;
+use crate::pipeline::{self, ParseInput, Stage};
+use crate::rule_database::{RuleDatabase, RuleList};
+use crate::trace::{self, Part, Reason};
 
 use crate::parsers::utils::{
     lazy_user_agent_match, static_user_agent_match, LazyRegex, SafeRegex as Regex,
@@ -65,6 +72,8 @@ pub enum DeviceType {
 
 static APPLE_OS_NAMES: [&str; 5] = ["iPadOS", "tvOS", "watchOS", "iOS", "Mac"];
 
+static AVAILABLE_BRANDS: Lazy<AvailableBrands> = Lazy::new(AvailableBrands::default);
+
 impl DeviceType {
     // these are used basically entirely for tests.
     pub fn as_str(&self) -> &'static str {
@@ -86,6 +95,7 @@ impl DeviceType {
             Self::Desktop => "desktop",
         }
     }
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(name: &str) -> DeviceType {
         match name {
             "desktop" => Self::Desktop,
@@ -123,9 +133,27 @@ pub struct Device {
     pub(crate) touch_enabled: bool,
 }
 
+impl Device {
+    /// A device detected by a parser of your own, see
+    /// [`Parser`](crate::pipeline::Parser).
+    pub fn new(
+        device_type: Option<DeviceType>,
+        brand: Option<String>,
+        model: Option<String>,
+    ) -> Self {
+        Self {
+            device_type,
+            brand,
+            model,
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct DeviceList {
     devices: Vec<(String, DeviceEntry)>,
+    prefilter: OnceCell<LiteralPrefilter>,
 }
 
 #[derive(Debug)]
@@ -197,17 +225,15 @@ fn get_device_type_from_form_factors(form_factors: &[String]) -> Option<DeviceTy
     None
 }
 
-pub fn lookup(
+pub(crate) fn lookup(
+    db: &RuleDatabase,
     ua: &str,
     client: Option<&Client>,
     client_hints: Option<&ClientHint>,
     os_info: Option<&OS>,
+    parsers: &[Stage<Device>],
+    enabled: impl Fn(ParserFamily) -> bool,
 ) -> Result<Option<Device>> {
-    static ANDROID_10_MODEL: Lazy<Regex> =
-        Lazy::new(|| Regex::new(r"(Android 10[.\d]*; K)(?: Build/|[;)])").unwrap());
-    // Matches reduced UAs for Android 11-15: "Android 11)" / "Android 12)" etc.
-    static ANDROID_REDUCED_UA: Lazy<Regex> =
-        Lazy::new(|| Regex::new(r"(Android 1[1-5])\) AppleWebKit").unwrap());
     // Matches desktop fragment used in client hints reduced UAs
     static DESKTOP_UA_FRAGMENT: Lazy<Regex> =
         Lazy::new(|| Regex::new(r"(X11; Linux x86_64)").unwrap());
@@ -217,20 +243,15 @@ pub fn lookup(
     let ua: Cow<'_, str> = 'ua: {
         if let Some(client_hints) = &client_hints {
             if let Some(model) = &client_hints.model {
-                // Android 10; K  →  Android {osVersion}; {model}
-                if let Some(captures) = ANDROID_10_MODEL.captures(ua)? {
+                // Android 10; K / Android 11..15)  →  Android {osVersion}; {model}
+                let reduced = accuracy::reduced_android(ua)?.filter(|reduced| {
+                    reduced.is_android_10_k() || (!reduced.k && (11..=15).contains(&reduced.major))
+                });
+                if let Some(reduced) = reduced {
                     let os_version: &str =
                         os_info.and_then(|os| os.version.as_deref()).unwrap_or("10");
                     let replacement = format!("Android {}; {}", os_version, model);
-                    let res = ua.replace(&captures[1], &replacement);
-                    break 'ua Cow::Owned(res);
-                }
-                // Android 11..15)  →  Android {osVersion}; {model})
-                if let Some(captures) = ANDROID_REDUCED_UA.captures(ua)? {
-                    let os_version: &str =
-                        os_info.and_then(|os| os.version.as_deref()).unwrap_or("10");
-                    let replacement = format!("Android {}; {}", os_version, model);
-                    let res = ua.replace(captures.get(1).unwrap().as_str(), &replacement);
+                    let res = ua.replace(reduced.text, &replacement);
                     break 'ua Cow::Owned(res);
                 }
                 // X11; Linux x86_64  →  X11; Linux x86_64; {model}
@@ -246,34 +267,35 @@ pub fn lookup(
     };
 
     let mut device = 'dev: {
-        if let Some(res) = televisions::lookup(&ua)? {
-            break 'dev res;
-        }
-
-        if let Some(res) = shell_tvs::lookup(&ua)? {
-            break 'dev res;
-        }
-        if let Some(res) = notebooks::lookup(&ua)? {
-            break 'dev res;
-        }
-
-        if let Some(res) = consoles::lookup(&ua)? {
-            break 'dev res;
-        }
-
-        if let Some(res) = car_browsers::lookup(&ua)? {
-            break 'dev res;
-        }
-
-        if let Some(res) = cameras::lookup(&ua)? {
-            break 'dev res;
-        }
-        if let Some(res) = portable_media_players::lookup(&ua)? {
-            break 'dev res;
-        }
+        for parser in parsers {
+            let res = match parser {
+                Stage::Builtin(family) if !enabled(*family) => None,
+                Stage::Builtin(ParserFamily::Television) => televisions::lookup(db, &ua)?,
+                Stage::Builtin(ParserFamily::ShellTv) => shell_tvs::lookup(db, &ua)?,
+                Stage::Builtin(ParserFamily::Notebook) => notebooks::lookup(db, &ua)?,
+                Stage::Builtin(ParserFamily::Console) => consoles::lookup(db, &ua)?,
+                Stage::Builtin(ParserFamily::CarBrowser) => car_browsers::lookup(db, &ua)?,
+                Stage::Builtin(ParserFamily::Camera) => cameras::lookup(db, &ua)?,
+                Stage::Builtin(ParserFamily::PortableMediaPlayer) => {
+                    portable_media_players::lookup(db, &ua)?
+                }
+                Stage::Builtin(ParserFamily::Mobile) => mobiles::lookup(db, &ua)?,
+                // client parsers, which never make it into this chain.
+                Stage::Builtin(_) => None,
+                Stage::Custom(parser) => {
+                    let input = ParseInput {
+                        ua: &ua,
+                        client_hints,
+                        os: os_info,
+                        client,
+                    };
+                    pipeline::run(Part::Device, &**parser, &input)?
+                }
+            };
 
-        if let Some(res) = mobiles::lookup(&ua)? {
-            break 'dev res;
+            if let Some(res) = res {
+                break 'dev res;
+            }
         }
 
         Device {
@@ -284,12 +306,18 @@ pub fn lookup(
         }
     };
 
+    if let Cow::Owned(_) = ua {
+        // the rules matched the model the hints restored rather than the user agent.
+        trace::changes(trace::snapshot::<Device>(None), Some(&device), Reason::ClientHints);
+    }
+
     static TOUCH: Lazy<Regex> = static_user_agent_match!(r#"Touch"#);
 
     if TOUCH.is_match(&ua)? {
         device.touch_enabled = true;
     }
 
+    let before = trace::snapshot(Some(&device));
     if let Some(client_hints) = client_hints {
         if device.model.is_none() && client_hints.model.is_some() {
             device.model = client_hints.model.clone();
@@ -299,7 +327,7 @@ pub fn lookup(
                 if let Some(model) = &device.model {
                     // Create a synthetic UA containing the model to trigger device detection
                     let model_ua = format!("Android; {}", model);
-                    if let Some(mobile_device) = mobiles::lookup(&model_ua)? {
+                    if let Some(mobile_device) = mobiles::lookup(db, &model_ua)? {
                         device.device_type = mobile_device.device_type;
                         if device.brand.is_none() {
                             device.brand = mobile_device.brand;
@@ -321,13 +349,17 @@ pub fn lookup(
             device.device_type = get_device_type_from_form_factors(&client_hints.form_factors);
         }
     }
+    trace::changes(before, Some(&device), Reason::ClientHints);
 
+    let before = trace::snapshot(Some(&device));
     if device.brand.is_none() {
-        if let Some(brand) = vendor_fragments::lookup(&ua)? {
+        if let Some(brand) = vendor_fragments::lookup(db, &ua)? {
             device.brand = Some(brand.to_owned());
         }
     }
+    trace::changes(before, Some(&device), Reason::VendorFragment);
 
+    let before = trace::snapshot(Some(&device));
     if let Some(os) = &os_info {
         if let Some(brand) = &device.brand {
             if brand == "Apple" && !APPLE_OS_NAMES.iter().any(|x| *x == os.name) {
@@ -337,12 +369,11 @@ pub fn lookup(
             }
         }
 
-        if device.brand.is_none() {
-            if APPLE_OS_NAMES.iter().any(|x| *x == os.name) {
-                device.brand = Some("Apple".to_owned());
-            }
+        if device.brand.is_none() && APPLE_OS_NAMES.iter().any(|x| *x == os.name) {
+            device.brand = Some("Apple".to_owned());
         }
     }
+    trace::changes(before, Some(&device), Reason::AppleOs);
 
     static APAD_TABLET: Lazy<Regex> = static_user_agent_match!(r#"Pad/APad"#);
     static ANDROID_TABLET: Lazy<Regex> =
@@ -353,6 +384,7 @@ pub fn lookup(
         static_user_agent_match!(r#"Android( [\.0-9]+)?; Mobile VR;| VR "#);
     static OPERA_TABLET: Lazy<Regex> = static_user_agent_match!(r#"Opera Tablet"#);
 
+    let before = trace::snapshot(Some(&device));
     if device.device_type.is_none() && ANDROID_VR.is_match(&ua)? {
         device.device_type = Some(DeviceType::Wearable);
     }
@@ -387,7 +419,9 @@ pub fn lookup(
     if device.device_type.is_none() && ANDROID_MOBILE.is_match(&ua)? {
         device.device_type = Some(DeviceType::SmartPhone);
     }
+    trace::changes(before, Some(&device), Reason::AndroidHeuristics);
 
+    let before = trace::snapshot(Some(&device));
     if let Some(os) = &os_info {
         static V2: Lazy<Version> = Lazy::new(|| Version::from("2.0").unwrap());
         static V3: Lazy<Version> = Lazy::new(|| Version::from("3.0").unwrap());
@@ -449,12 +483,14 @@ pub fn lookup(
             }
         }
     }
+    trace::changes(before, Some(&device), Reason::OsHeuristics);
 
     // Puffin browser device type detection patterns
     static PUFFIN_DESKTOP: Lazy<Regex> = static_user_agent_match!(r#"Puffin/(?:\d+[.\d]+)[LMW]D"#);
     static PUFFIN_SMARTPHONE: Lazy<Regex> = static_user_agent_match!(r#"Puffin/(?:\d+[.\d]+)[AIFLW]P"#);
     static PUFFIN_TABLET: Lazy<Regex> = static_user_agent_match!(r#"Puffin/(?:\d+[.\d]+)[AILW]T"#);
 
+    let before = trace::snapshot(Some(&device));
     // Check for Puffin browser device type patterns first
     if device.device_type.is_none() && PUFFIN_DESKTOP.is_match(&ua)? {
         device.device_type = Some(DeviceType::Desktop);
@@ -465,6 +501,7 @@ pub fn lookup(
     if device.device_type.is_none() && PUFFIN_TABLET.is_match(&ua)? {
         device.device_type = Some(DeviceType::Tablet);
     }
+    trace::changes(before, Some(&device), Reason::PuffinHeuristics);
 
     static OPERA: Lazy<Regex> = static_user_agent_match!(r#"Opera TV Store| OMI/"#);
     static ANDR0ID: Lazy<Regex> =
@@ -473,11 +510,12 @@ pub fn lookup(
     static GENERIC_TV: Lazy<Regex> = static_user_agent_match!(r#"\(TV;"#);
 
     // Only set TV type if device is not already detected as TV or Peripheral
-    let should_check_tv = match &device.device_type {
-        Some(DeviceType::Television) | Some(DeviceType::Peripheral) => false,
-        _ => true,
-    };
+    let should_check_tv = !matches!(
+        &device.device_type,
+        Some(DeviceType::Television) | Some(DeviceType::Peripheral)
+    );
     
+    let before = trace::snapshot(Some(&device));
     if should_check_tv {
         if OPERA.is_match(&ua)? {
             device.device_type = Some(DeviceType::Television);
@@ -519,10 +557,12 @@ pub fn lookup(
             device.device_type = Some(DeviceType::Television);
         }
     }
+    trace::changes(before, Some(&device), Reason::TvHeuristics);
 
     static DESKTOP_FRAGMENT: Lazy<Regex> =
         static_user_agent_match!(r#"Desktop(?: (x(?:32|64)|WOW64))?;"#);
 
+    let before = trace::snapshot(Some(&device));
     if let Some(device_type) = &device.device_type {
         if *device_type != DeviceType::Desktop
             && ua.contains("Desktop")
@@ -544,6 +584,18 @@ pub fn lookup(
     if device.device_type.is_none() && KAIOS_UA.is_match(&ua)? {
         device.device_type = Some(DeviceType::FeaturePhone);
     }
+    trace::changes(before, Some(&device), Reason::DesktopHeuristics);
+
+    // inserted parsers and overlays may spell a brand differently than upstream does.
+    let before = trace::snapshot(Some(&device));
+    if let Some(brand) = &device.brand {
+        if let Some(known) = AVAILABLE_BRANDS.search_by_name(brand) {
+            if known.name != *brand {
+                device.brand = Some(known.name.clone());
+            }
+        }
+    }
+    trace::changes(before, Some(&device), Reason::BrandNormalization);
 
     if device.device_type.is_none() && device.brand.is_none() && device.model.is_none() {
         Ok(None)
@@ -593,8 +645,39 @@ fn is_touch(ua: &str) -> Result<bool> {
 }
 
 impl DeviceList {
-    fn lookup(&self, ua: &str, _type: &str) -> Result<Option<Device>> {
-        for (name, device) in self.devices.iter() {
+    /// Every regex of this list along with the index of its rule, in rule order.
+    pub(crate) fn regexes(&self) -> impl Iterator<Item = (usize, &LazyRegex)> {
+        self.devices.iter().enumerate().flat_map(|(rule, (_, device))| {
+            std::iter::once(&device.regex)
+                .chain(device.models.iter().filter_map(|model| model.regex.as_ref()))
+                .map(move |regex| (rule, regex))
+        })
+    }
+
+    /// Every brand this list names along with the index of its rule, in rule order.
+    pub(crate) fn brands(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.devices.iter().enumerate().flat_map(|(rule, (name, device))| {
+            std::iter::once(name.as_str())
+                .chain(device.models.iter().filter_map(|model| model.brand.as_deref()))
+                .filter(|brand| *brand != "Unknown")
+                .map(move |brand| (rule, brand))
+        })
+    }
+
+    fn prefilter(&self) -> &LiteralPrefilter {
+        self.prefilter.get_or_init(|| {
+            LiteralPrefilter::new(self.devices.iter().map(|(_, device)| &device.regex))
+        })
+    }
+
+    pub(crate) fn lookup(&self, ua: &str, _type: &str) -> Result<Option<Device>> {
+        let candidates = self.prefilter().candidates(ua);
+
+        for (rule, (name, device)) in self.devices.iter().enumerate() {
+            if !candidates.contains(rule) {
+                continue;
+            }
+
             if let Some(match_result) = device.lookup(ua)? {
                 static TD: Lazy<Regex> = Lazy::new(|| Regex::new(r#" [Tt][Dd]$"#).unwrap());
 
@@ -647,8 +730,24 @@ impl DeviceList {
 
         Ok(None)
     }
+}
+
+impl RuleList for DeviceList {
+    fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    fn prepend(&mut self, overlay: Self) {
+        self.devices.splice(0..0, overlay.devices);
+        self.prefilter = OnceCell::new();
+    }
+}
 
-    fn from_file(file_contents: &str) -> Result<DeviceList> {
+impl<'de> Deserialize<'de> for DeviceList {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
         #[derive(Debug, Deserialize)]
         #[serde(try_from = "Value")]
         struct YamlModelEntry {
@@ -721,12 +820,15 @@ impl DeviceList {
                 // to get the right answer.
                 // devices.sort_by(|a, b| a.0.cmp(&b.0));
 
-                DeviceList { devices }
+                DeviceList {
+                    devices,
+                    prefilter: OnceCell::new(),
+                }
             }
         }
 
         impl TryFrom<Value> for YamlModelEntry {
-            type Error = anyhow::Error;
+            type Error = DetectError;
 
             fn try_from(value: Value) -> Result<Self, Self::Error> {
                 let model = match value {
@@ -739,7 +841,7 @@ impl DeviceList {
                     Value::Mapping(mappings) => {
                         let model = mappings["model"]
                             .as_str()
-                            .ok_or(anyhow::anyhow!("invalid model"))?;
+                            .ok_or_else(|| DetectError::rule_database("", "invalid model"))?;
 
                         let regex = mappings["regex"].as_str();
                         let device = mappings.get("device").and_then(|x| x.as_str());
@@ -752,13 +854,16 @@ impl DeviceList {
                             model: model.to_owned(),
                         }
                     }
-                    err => Err(anyhow::anyhow!("Invalid model {:?}", err))?,
+                    err => Err(DetectError::rule_database(
+                        "",
+                        format!("Invalid model {:?}", err),
+                    ))?,
                 };
                 Ok(model)
             }
         }
 
-        let res: YamlDeviceList = serde_yaml::from_str(file_contents)?;
+        let res = YamlDeviceList::deserialize(deserializer)?;
         Ok(res.into())
     }
 }
@@ -766,7 +871,14 @@ impl DeviceList {
 impl DeviceEntry {
     fn lookup(&self, ua: &str) -> Result<Option<DeviceMatchResult>> {
         let res = if let Some(captures) = self.regex.captures(ua)? {
-            if let Some(mut model) = self.model_match(ua)? {
+            let model = self.model_match(ua)?;
+            trace::model_rule(
+                Part::Device,
+                &self.regex,
+                model.as_ref().map(|(index, _)| *index),
+            );
+
+            if let Some((_, mut model)) = model {
                 let mut m = "".to_owned();
 
                 // php will treat $1[0-9]+ as $1 and so that bug constantly
@@ -804,11 +916,11 @@ impl DeviceEntry {
         Ok(res)
     }
 
-    fn model_match(&self, ua: &str) -> Result<Option<ModelMatchResult>> {
-        for model in self.models.iter() {
-            let res = model_match(model, ua)?;
-            if res.is_some() {
-                return Ok(res);
+    // the first model matching, along with its index.
+    fn model_match(&self, ua: &str) -> Result<Option<(usize, ModelMatchResult)>> {
+        for (index, model) in self.models.iter().enumerate() {
+            if let Some(res) = model_match(model, ua)? {
+                return Ok(Some((index, res)));
             }
         }
 
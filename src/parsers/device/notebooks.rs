@@ -1,24 +1,17 @@
-use anyhow::Result;
+use crate::error::Result;
 
-use super::{Device, DeviceList};
+use super::Device;
 use once_cell::sync::Lazy;
 
 use crate::parsers::utils::{static_user_agent_match, SafeRegex as Regex};
-
-static DEVICE_LIST: Lazy<DeviceList> = Lazy::new(|| {
-    let contents = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/regexes/device/notebooks.yml"
-    ));
-    DeviceList::from_file(contents).expect("loading notebooks.yml")
-});
+use crate::rule_database::RuleDatabase;
 
 static NOTEBOOK: Lazy<Regex> = static_user_agent_match!(r#"FBMD/"#);
 
-pub fn lookup(ua: &str) -> Result<Option<Device>> {
+pub fn lookup(db: &RuleDatabase, ua: &str) -> Result<Option<Device>> {
     if !NOTEBOOK.is_match(ua)? {
         return Ok(None);
     }
 
-    DEVICE_LIST.lookup(ua, "notebook")
+    db.notebooks.lookup(ua, "notebook")
 }
@@ -0,0 +1,53 @@
+//! Subscriber counts and feed ids, which feed fetchers report in their user agents, eg.
+//! `Feedfetcher-Google; (+http://www.google.com/feedfetcher.html; 19 subscribers; feed-id=1396)`.
+//!
+//! Rules may carry `subscribers` and `feed_id` regexes of their own, for user agents
+//! the defaults below miss. The value is the first group of the regex that matched.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Deserializer};
+
+use crate::error::Result;
+use crate::parsers::utils::{lazy_user_agent_match, LazyRegex};
+
+static SUBSCRIBERS: Lazy<LazyRegex> =
+    Lazy::new(|| lazy_user_agent_match(r"(\d+) subscribers|subscribers=(\d+)"));
+static FEED_ID: Lazy<LazyRegex> = Lazy::new(|| lazy_user_agent_match(r"feed-?id[=:] ?([\w-]+)"));
+
+/// The subscriber count and feed id of a detection, by the rule's own regexes, falling
+/// back to the defaults when `defaults` is set.
+pub(crate) fn lookup(
+    ua: &str,
+    subscribers: Option<&LazyRegex>,
+    feed_id: Option<&LazyRegex>,
+    defaults: bool,
+) -> Result<(Option<u64>, Option<String>)> {
+    let subscribers = match subscribers.or(defaults.then_some(&*SUBSCRIBERS)) {
+        Some(regex) => first_group(regex, ua)?.and_then(|count| count.parse().ok()),
+        None => None,
+    };
+    let feed_id = match feed_id.or(defaults.then_some(&*FEED_ID)) {
+        Some(regex) => first_group(regex, ua)?,
+        None => None,
+    };
+
+    Ok((subscribers, feed_id))
+}
+
+fn first_group(regex: &LazyRegex, ua: &str) -> Result<Option<String>> {
+    let Some(captures) = regex.captures(ua)? else {
+        return Ok(None);
+    };
+
+    Ok((1..captures.len())
+        .find_map(|group| captures.get(group))
+        .map(|group| group.as_str().to_owned()))
+}
+
+pub(crate) fn de_regex<'de, D>(deserializer: D) -> Result<Option<LazyRegex>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let regex = Option::<String>::deserialize(deserializer)?;
+    Ok(regex.map(|regex| lazy_user_agent_match(&regex)))
+}
@@ -1,22 +1,22 @@
-use anyhow::Result;
+use crate::error::Result;
 
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 use serde_yaml::Value;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use std::collections::HashMap;
 
+use crate::accuracy;
 use crate::client_hints::ClientHint;
 use crate::known_oss::AvailableOSs;
+use crate::parsers::prefilter::LiteralPrefilter;
 use crate::parsers::utils::{
     lazy_user_agent_match, static_user_agent_match, LazyRegex, SafeRegex as Regex,
 };
+use crate::rule_database::{RuleDatabase, RuleList};
+use crate::trace::{self, Part, Reason};
 
-static OS_LIST: Lazy<OSList> = Lazy::new(|| {
-    let contents = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/regexes/oss.yml"));
-    OSList::from_file(contents).expect("loading oss.yml")
-});
 static CLIENT_HINT_MAPPING: Lazy<Vec<(String, Vec<String>)>> = Lazy::new(|| {
     [("GNU/Linux", vec!["Linux"]), ("Mac", vec!["MacOS"])]
         .into_iter()
@@ -81,68 +81,84 @@ pub struct OS {
     pub version: Option<String>,
     pub platform: Option<String>,
     pub family: Option<String>,
+    /// Upstream's short code, eg. `AND` for Android.
+    pub short_name: Option<String>,
 
     #[serde(skip)]
     pub(crate) desktop: bool,
 }
 
-pub fn lookup(ua: &str, client_hints: Option<&ClientHint>) -> Result<Option<OS>> {
-    let mut os_from_hints: Option<OS> = client_hints.and_then(|client_hints| {
-        if let Some(platform) = client_hints.platform.as_ref() {
-            let hint = CLIENT_HINT_MAPPING
-                .iter()
-                .find(|(_k, v)| {
-                    v.iter()
-                        .any(|v| *v.to_lowercase() == platform.to_lowercase())
-                })
-                .map(|x| &x.0)
-                .or(Some(platform));
-
-            if let Some(os) = hint.and_then(|hint| AVAILABLE_OSSES.search_by_name(hint)) {
-                let mut version = client_hints.platform_version.clone();
-
-                if let Some(platform_version) = &client_hints.platform_version {
-                    if os.name == "Windows" {
-                        let parts: Vec<&str> = platform_version.split('.').collect();
-                        let major_version = parts.first().and_then(|x| x.parse::<u32>().ok()).unwrap_or(0);
-                        let minor_version = parts.get(1).and_then(|x| x.parse::<u32>().ok()).unwrap_or(0);
-
-                        if major_version == 0 {
-                            // 0.x.0 maps to older Windows versions
-                            version = match minor_version {
-                                1 => Some("7".to_owned()),
-                                2 => Some("8".to_owned()),
-                                3 => Some("8.1".to_owned()),
-                                _ => version, // keep as "0.0.0" etc. for later adjustment
-                            };
-                        } else if major_version > 0 && major_version < 11 {
-                            version = Some("10".to_owned());
-                        } else if major_version > 10 {
-                            version = Some("11".to_owned());
-                        }
+/// The os `Sec-CH-UA-Platform` names, with the version of `Sec-CH-UA-Platform-Version`
+/// mapped to the os version where they differ, eg. Windows.
+pub(crate) fn os_from_hints(client_hints: &ClientHint) -> Option<OS> {
+    if let Some(platform) = client_hints.platform.as_ref() {
+        let hint = CLIENT_HINT_MAPPING
+            .iter()
+            .find(|(_k, v)| {
+                v.iter()
+                    .any(|v| *v.to_lowercase() == platform.to_lowercase())
+            })
+            .map(|x| &x.0)
+            .or(Some(platform));
+
+        if let Some(os) = hint.and_then(|hint| AVAILABLE_OSSES.search_by_name(hint)) {
+            let mut version = client_hints.platform_version.clone();
+
+            if let Some(platform_version) = &client_hints.platform_version {
+                if os.name == "Windows" {
+                    let parts: Vec<&str> = platform_version.split('.').collect();
+                    let major_version = parts.first().and_then(|x| x.parse::<u32>().ok()).unwrap_or(0);
+                    let minor_version = parts.get(1).and_then(|x| x.parse::<u32>().ok()).unwrap_or(0);
+
+                    if major_version == 0 {
+                        // 0.x.0 maps to older Windows versions
+                        version = match minor_version {
+                            1 => Some("7".to_owned()),
+                            2 => Some("8".to_owned()),
+                            3 => Some("8.1".to_owned()),
+                            _ => version, // keep as "0.0.0" etc. for later adjustment
+                        };
+                    } else if major_version > 0 && major_version < 11 {
+                        version = Some("10".to_owned());
+                    } else if major_version > 10 {
+                        version = Some("11".to_owned());
                     }
                 }
-
-                Some(OS {
-                    name: os.name.clone(),
-                    version,
-                    platform: None,
-                    family: os.family.clone(),
-                    desktop: os.desktop,
-                })
-            } else {
-                None
             }
+
+            Some(OS {
+                name: os.name.clone(),
+                version,
+                platform: None,
+                family: os.family.clone(),
+                short_name: None,
+                desktop: os.desktop,
+            })
         } else {
             None
         }
-    });
+    } else {
+        None
+    }
+}
+
+pub fn lookup(
+    db: &RuleDatabase,
+    ua: &str,
+    client_hints: Option<&ClientHint>,
+) -> Result<Option<OS>> {
+    let mut os_from_hints: Option<OS> = client_hints.and_then(os_from_hints);
 
     // Restore UA from client hints model name for accurate detection (e.g., Chrome OS codenames)
     let restored_ua = restore_ua_from_client_hints(ua, client_hints);
     let effective_ua = restored_ua.as_deref().unwrap_or(ua);
 
-    let os_from_ua: Option<OS> = OS_LIST.lookup(effective_ua)?;
+    let os_from_ua: Option<OS> = db.oss.lookup(effective_ua)?;
+    if restored_ua.is_some() {
+        // the rule matched what the hints restored rather than the user agent.
+        trace::changes(trace::snapshot::<OS>(None), os_from_ua.as_ref(), Reason::ClientHints);
+    }
+    let before = trace::snapshot(os_from_ua.as_ref());
 
     // various occasional overrides of client hint information based on ua.
     if let Some(ref mut os_from_hints) = &mut os_from_hints {
@@ -223,6 +239,9 @@ pub fn lookup(ua: &str, client_hints: Option<&ClientHint>) -> Result<Option<OS>>
     }
 
     let mut res = os_from_hints.or(os_from_ua.clone());
+    trace::changes(before, res.as_ref(), Reason::ClientHints);
+
+    let before = trace::snapshot(res.as_ref());
 
     if let Some(os) = &mut res {
         if let platform @ Some(_) = parse_platform(effective_ua, client_hints)? {
@@ -268,7 +287,7 @@ pub fn lookup(ua: &str, client_hints: Option<&ClientHint>) -> Result<Option<OS>>
                         .get(os.version.as_deref().unwrap_or_else(|| {
                             os.version
                                 .as_deref()
-                                .map(|x| x.split('.').last().unwrap_or("0"))
+                                .map(|x| x.split('.').next_back().unwrap_or("0"))
                                 .unwrap_or("0")
                         }))
                         .map(|x| (*x).to_owned());
@@ -307,19 +326,17 @@ pub fn lookup(ua: &str, client_hints: Option<&ClientHint>) -> Result<Option<OS>>
                 os.version = None;
             }
         }
+
+        os.short_name = AVAILABLE_OSSES
+            .by_name(&os.name)
+            .map(|available| available.short_name.clone());
     }
+    trace::changes(before, res.as_ref(), Reason::PostProcessing);
 
     Ok(res)
 }
 
 fn restore_ua_from_client_hints(ua: &str, client_hints: Option<&ClientHint>) -> Option<String> {
-    static ANDROID_K_DETECT: Lazy<Regex> = Lazy::new(|| {
-        Regex::new(r"(?i)Android (?:1[0-6][.\d]*; K(?:\s+Build/|[;)])|1[0-6]\))\s*AppleWebKit")
-            .expect("android k detect regex")
-    });
-    static ANDROID_K_REPLACE: Lazy<Regex> = Lazy::new(|| {
-        Regex::new(r"(?i)Android (?:10[.\d]*; K|1[1-6])").expect("android k replace regex")
-    });
     static DESKTOP_DETECT: Lazy<Regex> = Lazy::new(|| {
         Regex::new(r"(?:Windows (?:NT|IoT)|X11; Linux x86_64)").expect("desktop detect regex")
     });
@@ -333,13 +350,12 @@ fn restore_ua_from_client_hints(ua: &str, client_hints: Option<&ClientHint>) ->
     let hints = client_hints?;
     let model = hints.model.as_ref().filter(|m| !m.is_empty())?;
 
-    if ANDROID_K_DETECT.is_match(ua).unwrap_or(false)
-        && !ua.to_lowercase().contains("telegram-android/")
-    {
-        let os_version = hints.platform_version.as_deref().unwrap_or("10");
-        let replacement = format!("Android {}; {}", os_version, model);
-        let result = ANDROID_K_REPLACE.replace_all(ua, replacement.as_str());
-        return Some(result.into_owned());
+    if let Some(reduced) = accuracy::reduced_android(ua).unwrap_or(None) {
+        if !ua.to_lowercase().contains("telegram-android/") {
+            let os_version = hints.platform_version.as_deref().unwrap_or("10");
+            let replacement = format!("Android {}; {}", os_version, model);
+            return Some(ua.replace(reduced.text, &replacement));
+        }
     }
 
     if DESKTOP_DETECT.is_match(ua).unwrap_or(false)
@@ -438,8 +454,10 @@ fn parse_platform(ua: &str, client_hints: Option<&ClientHint>) -> Result<Option<
     Ok(None)
 }
 
-struct OSList {
+#[derive(Debug)]
+pub(crate) struct OSList {
     oss: Vec<OSEntry>,
+    prefilter: OnceCell<LiteralPrefilter>,
 }
 
 #[derive(Debug)]
@@ -511,17 +529,54 @@ impl OSEntry {
 }
 
 impl OSList {
-    fn lookup(&self, ua: &str) -> Result<Option<OS>> {
-        for os in self.oss.iter() {
+    /// Every regex of this list along with the index of its rule, in rule order.
+    pub(crate) fn regexes(&self) -> impl Iterator<Item = (usize, &LazyRegex)> {
+        self.oss.iter().enumerate().flat_map(|(rule, os)| {
+            std::iter::once(&os.regex)
+                .chain(os.versions.iter().map(|version| &version.regex))
+                .map(move |regex| (rule, regex))
+        })
+    }
+
+    fn prefilter(&self) -> &LiteralPrefilter {
+        self.prefilter
+            .get_or_init(|| LiteralPrefilter::new(self.oss.iter().map(|os| &os.regex)))
+    }
+
+    pub(crate) fn lookup(&self, ua: &str) -> Result<Option<OS>> {
+        let candidates = self.prefilter().candidates(ua);
+
+        for (rule, os) in self.oss.iter().enumerate() {
+            if !candidates.contains(rule) {
+                continue;
+            }
+
             if let Some(res) = os.is_match(ua)? {
+                trace::rule(Part::Os, &os.regex);
                 return Ok(Some(res));
             }
         }
 
         Ok(None)
     }
+}
+
+impl RuleList for OSList {
+    fn len(&self) -> usize {
+        self.oss.len()
+    }
 
-    fn from_file(contents: &str) -> Result<OSList> {
+    fn prepend(&mut self, overlay: Self) {
+        self.oss.splice(0..0, overlay.oss);
+        self.prefilter = OnceCell::new();
+    }
+}
+
+impl<'de> Deserialize<'de> for OSList {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
         #[derive(Debug, Deserialize)]
         #[serde(try_from = "Value")]
         struct YamlVersion {
@@ -603,11 +658,12 @@ impl OSList {
             fn into(self) -> OSList {
                 OSList {
                     oss: self.oss.into_iter().map(|x| x.into()).collect(),
+                    prefilter: OnceCell::new(),
                 }
             }
         }
 
-        let res: YamlOSList = serde_yaml::from_str(contents)?;
+        let res = YamlOSList::deserialize(deserializer)?;
         Ok(res.into())
     }
 }
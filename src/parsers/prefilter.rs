@@ -0,0 +1,213 @@
+use aho_corasick::AhoCorasick;
+use regex_syntax::hir::{Class, Hir, HirKind};
+
+use std::collections::HashMap;
+
+use crate::parsers::utils::LazyRegex;
+
+// Literals shorter than this turn up in nearly every user agent, so they would
+// only cost time without ruling anything out.
+const MIN_LITERAL_LEN: usize = 2;
+
+/// Rules out, with a single pass over the user agent, every rule of a list that
+/// can't possibly match it, because a literal every match of its regex must contain
+/// is missing. Lists still walk the remaining rules in order, so the first match wins
+/// exactly as before.
+#[derive(Debug)]
+pub(crate) struct LiteralPrefilter {
+    rules: usize,
+    // rules we couldn't pull required literals from, these are always tried.
+    unfiltered: Vec<usize>,
+    searcher: Option<AhoCorasick>,
+    // for each literal in the searcher, the rules requiring it.
+    literal_rules: Vec<Vec<usize>>,
+}
+
+/// The rules that may match a particular user agent.
+pub(crate) struct Candidates {
+    // None when every rule is a candidate
+    rules: Option<Vec<bool>>,
+}
+
+impl Candidates {
+    pub(crate) fn contains(&self, rule: usize) -> bool {
+        self.rules.as_ref().map(|rules| rules[rule]).unwrap_or(true)
+    }
+}
+
+impl LiteralPrefilter {
+    /// Builds an index over the regexes of a list, in rule order.
+    pub(crate) fn new<'a>(regexes: impl Iterator<Item = &'a LazyRegex>) -> Self {
+        let mut rules = 0;
+        let mut unfiltered = Vec::new();
+        let mut literals: Vec<String> = Vec::new();
+        let mut literal_ids: HashMap<String, usize> = HashMap::new();
+        let mut literal_rules: Vec<Vec<usize>> = Vec::new();
+
+        for (rule, regex) in regexes.enumerate() {
+            rules += 1;
+
+            match required_literals(&regex.pattern) {
+                None => unfiltered.push(rule),
+                Some(required) => {
+                    for literal in required {
+                        let id = *literal_ids.entry(literal.clone()).or_insert_with(|| {
+                            literals.push(literal);
+                            literal_rules.push(Vec::new());
+                            literals.len() - 1
+                        });
+                        literal_rules[id].push(rule);
+                    }
+                }
+            }
+        }
+
+        let searcher = if literals.is_empty() {
+            None
+        } else {
+            Some(
+                AhoCorasick::builder()
+                    .ascii_case_insensitive(true)
+                    .build(&literals)
+                    .expect("literal prefilter"),
+            )
+        };
+
+        Self {
+            rules,
+            unfiltered,
+            searcher,
+            literal_rules,
+        }
+    }
+
+    pub(crate) fn candidates(&self, ua: &str) -> Candidates {
+        // Case insensitive regexes also match a few non ascii characters, such as
+        // the kelvin sign for k, which an ascii case insensitive search would miss.
+        let searcher = match &self.searcher {
+            Some(searcher) if ua.is_ascii() => searcher,
+            _ => return Candidates { rules: None },
+        };
+
+        let mut rules = vec![false; self.rules];
+        for &rule in self.unfiltered.iter() {
+            rules[rule] = true;
+        }
+
+        let mut seen = vec![false; self.literal_rules.len()];
+        for found in searcher.find_overlapping_iter(ua) {
+            let literal = found.pattern().as_usize();
+            if !seen[literal] {
+                seen[literal] = true;
+                for &rule in self.literal_rules[literal].iter() {
+                    rules[rule] = true;
+                }
+            }
+        }
+
+        Candidates { rules: Some(rules) }
+    }
+}
+
+/// Lowercase literals of which every match of `pattern` must contain at least one,
+/// when there are any worth searching for. Patterns the `regex` parser doesn't
+/// understand, such as those using look arounds, are never filtered.
+fn required_literals(pattern: &str) -> Option<Vec<String>> {
+    let hir = regex_syntax::Parser::new().parse(pattern).ok()?;
+
+    let literals = required(&hir)?;
+    if literals
+        .iter()
+        .all(|literal| literal.len() >= MIN_LITERAL_LEN)
+    {
+        Some(literals)
+    } else {
+        None
+    }
+}
+
+fn required(hir: &Hir) -> Option<Vec<String>> {
+    match hir.kind() {
+        HirKind::Literal(_) | HirKind::Class(_) => single(hir).map(|literal| vec![literal]),
+        HirKind::Capture(capture) => required(&capture.sub),
+        HirKind::Repetition(repetition) if repetition.min > 0 => required(&repetition.sub),
+        HirKind::Alternation(alternatives) => {
+            let mut literals = Vec::new();
+            for alternative in alternatives.iter() {
+                literals.extend(required(alternative)?);
+            }
+            Some(literals)
+        }
+        HirKind::Concat(parts) => {
+            // every part must match, so any one part's literals will do, pick the
+            // longest. Runs of single characters are joined into one literal.
+            let mut best: Option<Vec<String>> = None;
+            let mut run = String::new();
+
+            for part in parts.iter() {
+                match single(part) {
+                    Some(literal) => run.push_str(&literal),
+                    None => {
+                        best = better(best, std::mem::take(&mut run));
+                        best = better_of(best, required(part));
+                    }
+                }
+            }
+
+            better(best, run)
+        }
+        _ => None,
+    }
+}
+
+// A literal, or a class only matching one ascii character in either case, as (?i)
+// turns every letter into.
+fn single(hir: &Hir) -> Option<String> {
+    match hir.kind() {
+        HirKind::Literal(literal) => {
+            let literal = std::str::from_utf8(&literal.0).ok()?;
+            Some(literal.to_ascii_lowercase())
+        }
+        HirKind::Class(Class::Unicode(class)) => {
+            let mut single = None;
+            for range in class.iter().filter(|range| range.start().is_ascii()) {
+                for c in range.start()..=range.end().min('\x7f') {
+                    let c = c.to_ascii_lowercase();
+                    match single {
+                        None => single = Some(c),
+                        Some(s) if s == c => {}
+                        Some(_) => return None,
+                    }
+                }
+            }
+            single.map(String::from)
+        }
+        _ => None,
+    }
+}
+
+fn shortest(literals: &[String]) -> usize {
+    literals.iter().map(|x| x.len()).min().unwrap_or(0)
+}
+
+fn better(best: Option<Vec<String>>, run: String) -> Option<Vec<String>> {
+    if run.is_empty() {
+        best
+    } else {
+        better_of(best, Some(vec![run]))
+    }
+}
+
+fn better_of(a: Option<Vec<String>>, b: Option<Vec<String>>) -> Option<Vec<String>> {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            if shortest(&b) > shortest(&a) {
+                Some(b)
+            } else {
+                Some(a)
+            }
+        }
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
@@ -1,6 +1,6 @@
-use anyhow::Result;
+use crate::error::{DetectError, Result};
 
-use fancy_regex::{CaptureMatches, Captures, Error, Expander, Regex, Replacer};
+use itertools::Either;
 use once_cell::sync::Lazy;
 
 use once_cell::sync::OnceCell;
@@ -11,6 +11,7 @@ pub(crate) struct LazyRegex {
     regex: OnceCell<SafeRegex>,
 }
 use std::collections::HashMap;
+use std::ops::Index;
 use std::sync::Arc;
 use std::sync::RwLock;
 
@@ -18,45 +19,218 @@ use std::borrow::Cow;
 
 /// This is a regex that won't crash due to run time errors on match.
 /// This can still crash if passed an invalid regex in the first place.
+///
+/// Patterns are compiled with the linear time `regex` crate whenever it accepts them,
+/// only those it rejects, such as patterns with look arounds or back references, fall
+/// back to fancy-regex and its backtracking engine.
 #[derive(Debug)]
 pub(crate) struct SafeRegex {
-    regex: Regex,
+    engine: Engine,
+}
+
+#[derive(Debug)]
+enum Engine {
+    Linear(regex::Regex),
+    Backtracking(fancy_regex::Regex),
+}
+
+/// Capture groups of a match, from either engine.
+#[derive(Debug)]
+pub(crate) enum Captures<'t> {
+    Linear(regex::Captures<'t>),
+    Backtracking(fancy_regex::Captures<'t>),
+}
+
+/// A single capture group of a match.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Match<'t> {
+    text: &'t str,
+}
+
+impl<'t> Match<'t> {
+    pub fn as_str(&self) -> &'t str {
+        self.text
+    }
 }
 
 impl SafeRegex {
-    fn squash_runtime_error<T>(err: Result<T, Error>, ret: T) -> Result<T, Error> {
-        // this is either a stack overflow or a backtrack limit reached.
-        // in either case, we don't want to crash, just deny a match and move on.
-        match err {
-            Err(Error::RuntimeError(_)) => Ok(ret),
-            err => err,
+    pub fn new(pattern: &str) -> Result<Self> {
+        let engine = match regex::Regex::new(pattern) {
+            Ok(regex) => Engine::Linear(regex),
+            Err(_) => Engine::Backtracking(
+                fancy_regex::Regex::new(pattern)
+                    .map_err(|err| DetectError::regex_compile(pattern, err))?,
+            ),
+        };
+        Ok(Self { engine })
+    }
+
+    // either a stack overflow or the backtrack limit reached, reported rather than
+    // taken for no match, which would quietly detect something else.
+    fn runtime_error(regex: &fancy_regex::Regex, err: fancy_regex::Error) -> DetectError {
+        DetectError::RegexLimit {
+            pattern: regex.as_str().to_owned(),
+            message: err.to_string(),
         }
     }
 
-    pub fn new(pattern: &str) -> Result<Self> {
-        let regex = Regex::new(pattern)?;
-        Ok(Self { regex })
+    /// Whether this pattern had to be compiled with the backtracking engine, which
+    /// may give up on a match, rather than the linear time one.
+    pub fn needs_backtracking(&self) -> bool {
+        matches!(self.engine, Engine::Backtracking(_))
     }
 
     pub fn is_match(&self, text: &str) -> Result<bool> {
-        let res = Self::squash_runtime_error(self.regex.is_match(text), false)?;
-        Ok(res)
+        match &self.engine {
+            Engine::Linear(regex) => Ok(regex.is_match(text)),
+            Engine::Backtracking(regex) => regex
+                .is_match(text)
+                .map_err(|err| Self::runtime_error(regex, err)),
+        }
     }
 
     pub fn captures<'t>(&self, text: &'t str) -> Result<Option<Captures<'t>>> {
-        let res = Self::squash_runtime_error(self.regex.captures(text), None)?;
-        Ok(res)
+        match &self.engine {
+            Engine::Linear(regex) => Ok(regex.captures(text).map(Captures::Linear)),
+            Engine::Backtracking(regex) => {
+                let res = regex
+                    .captures(text)
+                    .map_err(|err| Self::runtime_error(regex, err))?;
+                Ok(res.map(Captures::Backtracking))
+            }
+        }
+    }
+
+    /// Replaces every match, expanding `$1`, `${name}` etc. in `rep` the way
+    /// fancy-regex does, whichever engine is used.
+    pub fn replace_all<'t>(&self, text: &'t str, rep: &str) -> Cow<'t, str> {
+        match &self.engine {
+            Engine::Linear(regex) => regex.replace_all(text, |caps: &regex::Captures<'_>| {
+                let mut dst = String::new();
+                append_expansion(&mut dst, rep, |name| {
+                    caps.name(name)
+                        .or_else(|| name.parse().ok().and_then(|num| caps.get(num)))
+                        .map(|m| m.as_str())
+                });
+                dst
+            }),
+            Engine::Backtracking(regex) => regex.replace_all(text, rep),
+        }
+    }
+
+    pub fn captures_iter<'r, 'h>(
+        &'r self,
+        haystack: &'h str,
+    ) -> impl Iterator<Item = Result<Captures<'h>>> + 'r
+    where
+        'h: 'r,
+    {
+        match &self.engine {
+            Engine::Linear(regex) => Either::Left(
+                regex
+                    .captures_iter(haystack)
+                    .map(|caps| Ok(Captures::Linear(caps))),
+            ),
+            Engine::Backtracking(regex) => {
+                Either::Right(regex.captures_iter(haystack).map(move |caps| {
+                    caps.map(Captures::Backtracking)
+                        .map_err(|err| Self::runtime_error(regex, err))
+                }))
+            }
+        }
+    }
+}
+
+impl<'t> Captures<'t> {
+    /// The number of groups, including the whole match.
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Captures::Linear(caps) => caps.len(),
+            Captures::Backtracking(caps) => caps.len(),
+        }
+    }
+
+    pub fn get(&self, i: usize) -> Option<Match<'t>> {
+        let text = match self {
+            Captures::Linear(caps) => caps.get(i).map(|m| m.as_str()),
+            Captures::Backtracking(caps) => caps.get(i).map(|m| m.as_str()),
+        };
+        text.map(|text| Match { text })
     }
 
-    pub fn replace_all<'t, R: Replacer>(&self, text: &'t str, rep: R) -> Cow<'t, str> {
-        self.regex.replace_all(text, rep)
+    pub fn name(&self, name: &str) -> Option<Match<'t>> {
+        let text = match self {
+            Captures::Linear(caps) => caps.name(name).map(|m| m.as_str()),
+            Captures::Backtracking(caps) => caps.name(name).map(|m| m.as_str()),
+        };
+        text.map(|text| Match { text })
     }
 
-    pub fn captures_iter<'r, 'h>(&'r self, haystack: &'h str) -> CaptureMatches<'r, 'h> {
-        self.regex.captures_iter(haystack)
+    /// Expands `$1`, `${name}` etc. in `template` into `dst`, exactly like
+    /// fancy-regex's default `Expander`.
+    pub fn expand(&self, template: &str, dst: &mut String) {
+        append_expansion(dst, template, |name| {
+            self.name(name)
+                .or_else(|| name.parse().ok().and_then(|num| self.get(num)))
+                .map(|m| m.as_str())
+        });
+    }
+}
+
+impl Index<usize> for Captures<'_> {
+    type Output = str;
+
+    fn index(&self, i: usize) -> &str {
+        self.get(i)
+            .map(|m| m.as_str())
+            .unwrap_or_else(|| panic!("no group at index '{}'", i))
     }
 }
 
+// Mirrors fancy_regex::Expander::default(), which only works on fancy-regex's own
+// captures: `$$` is a literal `$`, `$name` and `${name}` a group by name or number,
+// missing groups expand to nothing, and a `$` followed by anything else is kept.
+fn append_expansion<'c>(dst: &mut String, template: &str, group: impl Fn(&str) -> Option<&'c str>) {
+    fn is_id_char(c: char) -> bool {
+        c.is_alphanumeric() || c == '_'
+    }
+
+    let mut rest = template;
+    while let Some(pos) = rest.find('$') {
+        dst.push_str(&rest[..pos]);
+        let tail = &rest[pos + 1..];
+
+        if let Some(tail) = tail.strip_prefix('$') {
+            dst.push('$');
+            rest = tail;
+            continue;
+        }
+
+        let delimited = tail.strip_prefix('{').and_then(|inner| {
+            let len = inner.find(|c| !is_id_char(c))?;
+            (len > 0 && inner[len..].starts_with('}')).then(|| (&inner[..len], len + 2))
+        });
+        let id = delimited.or_else(|| {
+            let len = tail.find(|c| !is_id_char(c)).unwrap_or(tail.len());
+            (len > 0).then(|| (&tail[..len], len))
+        });
+
+        match id {
+            Some((name, skip)) => {
+                if let Some(text) = group(name) {
+                    dst.push_str(text);
+                }
+                rest = &tail[skip..];
+            }
+            None => {
+                dst.push('$');
+                rest = tail;
+            }
+        }
+    }
+    dst.push_str(rest);
+}
+
 pub(crate) struct LimitedUserMatchRegex {
     limit: usize,
     hm: Arc<RwLock<HashMap<String, Arc<SafeRegex>>>>,
@@ -70,23 +244,22 @@ impl LazyRegex {
         }
     }
 
-    pub(crate) fn is_match(&self, text: &str) -> Result<bool> {
-        let regex = self.regex.get_or_try_init(|| {
-            // println!("is_match compilation: {}", &self.pattern);
+    /// Compiles the regex now rather than on first use.
+    pub(crate) fn compile(&self) -> Result<&SafeRegex> {
+        self.regex.get_or_try_init(|| {
+            // println!("compilation: {}", &self.pattern);
             SafeRegex::new(&self.pattern)
-        })?;
-        regex.is_match(text)
+        })
+    }
+
+    pub(crate) fn is_match(&self, text: &str) -> Result<bool> {
+        self.compile()?.is_match(text)
     }
 
     pub(crate) fn captures<'t>(&self, text: &'t str) -> Result<Option<Captures<'t>>> {
-        let regex = self.regex.get_or_try_init(|| {
-            // println!("captures compilation: {}", &self.pattern);
-            SafeRegex::new(&self.pattern)
-        })?;
-        regex.captures(text)
+        self.compile()?.captures(text)
     }
 }
-
 impl LimitedUserMatchRegex {
     /// Creates a new LimitedUserMatchRegex with the given limit. It will go beyond
     /// that limit, but will warn on every new entry. At double this limit, it will
@@ -157,11 +330,13 @@ pub(crate) fn expand(template: &str, dst: &mut String, captures: &Captures<'_>)
     // https://docs.rs/fancy-regex/latest/fancy_regex/struct.Expander.html
     //
     // would just use `caps.expand(&template, &mut dst);`
-    static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\$([1-9])").unwrap());
+    static RE: Lazy<regex::Regex> = Lazy::new(|| regex::Regex::new(r"\$([1-9])").unwrap());
 
     //dbg!(template);
-    let template = RE.replace_all(template, |caps: &Captures<'_>| format!("${{{}}}", &caps[1]));
+    let template = RE.replace_all(template, |caps: &regex::Captures<'_>| {
+        format!("${{{}}}", &caps[1])
+    });
 
     //dbg!(&dst, &*template, &captures);
-    Expander::default().append_expansion(dst, &template, captures);
+    captures.expand(&template, dst);
 }
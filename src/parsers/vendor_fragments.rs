@@ -1,11 +1,10 @@
-use anyhow::Result;
-use serde::Deserialize;
-
-use once_cell::sync::Lazy;
-use std::collections::HashMap;
+use crate::error::Result;
+use serde::{Deserialize, Deserializer};
 
 use super::utils::lazy_user_agent_match;
 use crate::parsers::utils::LazyRegex;
+use crate::rule_database::{RuleDatabase, RuleList};
+use crate::trace::{self, Part};
 
 #[derive(Debug)]
 struct VendorFragments {
@@ -13,20 +12,12 @@ struct VendorFragments {
     fragments: Vec<LazyRegex>,
 }
 
-static FRAGMENT_LIST: Lazy<VendorFragmentList> = Lazy::new(|| {
-    let contents = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/regexes/vendorfragments.yml"
-    ));
-    VendorFragmentList::from_file(contents).expect("loading vendorfragments.yml")
-});
-
-pub fn lookup(ua: &str) -> Result<Option<&str>> {
-    FRAGMENT_LIST.lookup(ua)
+pub fn lookup<'a>(db: &'a RuleDatabase, ua: &str) -> Result<Option<&'a str>> {
+    db.vendor_fragments.lookup(ua)
 }
 
 #[derive(Debug)]
-struct VendorFragmentList {
+pub(crate) struct VendorFragmentList {
     list: Vec<VendorFragments>,
 }
 
@@ -34,6 +25,7 @@ impl VendorFragments {
     fn is_match(&self, ua: &str) -> Result<bool> {
         for x in self.fragments.iter() {
             if x.is_match(ua)? {
+                trace::rule(Part::Device, x);
                 return Ok(true);
             }
         }
@@ -42,6 +34,22 @@ impl VendorFragments {
 }
 
 impl VendorFragmentList {
+    /// Every regex of this list along with the index of its rule, in rule order.
+    pub(crate) fn regexes(&self) -> impl Iterator<Item = (usize, &LazyRegex)> {
+        self.list
+            .iter()
+            .enumerate()
+            .flat_map(|(rule, vendor)| vendor.fragments.iter().map(move |regex| (rule, regex)))
+    }
+
+    /// Every brand of this list along with the index of its rule.
+    pub(crate) fn brands(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.list
+            .iter()
+            .enumerate()
+            .map(|(rule, vendor)| (rule, vendor.vendor.as_str()))
+    }
+
     fn lookup(&self, ua: &str) -> Result<Option<&str>> {
         for x in self.list.iter() {
             if x.is_match(ua)? {
@@ -50,12 +58,28 @@ impl VendorFragmentList {
         }
         Ok(None)
     }
+}
+
+impl RuleList for VendorFragmentList {
+    fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    fn prepend(&mut self, overlay: Self) {
+        self.list.splice(0..0, overlay.list);
+    }
+}
 
-    fn from_file(contents: &str) -> Result<VendorFragmentList> {
+impl<'de> Deserialize<'de> for VendorFragmentList {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
         #[derive(Debug, Deserialize)]
         #[serde(transparent)]
         struct YamlVendorFragmentList {
-            list: HashMap<String, Vec<String>>,
+            // in file order, the first vendor matching wins.
+            list: indexmap::IndexMap<String, Vec<String>>,
         }
 
         #[allow(clippy::from_over_into)]
@@ -80,7 +104,7 @@ impl VendorFragmentList {
             }
         }
 
-        let res: YamlVendorFragmentList = serde_yaml::from_str(contents)?;
+        let res = YamlVendorFragmentList::deserialize(deserializer)?;
         Ok(res.into())
     }
 }
@@ -0,0 +1,177 @@
+//! Adding parsers of your own to the chains of client and device parsers, and hooks
+//! rewriting a detection once it is done, see
+//! [`DeviceDetectorBuilder::insert_client_parser`] and friends.
+//!
+//! [`DeviceDetectorBuilder::insert_client_parser`]: crate::device_detector::DeviceDetectorBuilder::insert_client_parser
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::client_hints::ClientHint;
+use crate::device_detector::{KnownDevice, ParserFamily};
+use crate::error::{DetectError, Result};
+use crate::parsers::client::Client;
+use crate::parsers::device::Device;
+use crate::parsers::oss::OS;
+use crate::trace::{self, Part};
+
+/// What a parser gets to detect from.
+#[derive(Clone, Copy, Debug)]
+pub struct ParseInput<'a> {
+    /// The user agent, as the built in parsers of the same chain see it. Device
+    /// parsers see the model from client hints filled in for reduced user agents.
+    pub ua: &'a str,
+    pub client_hints: Option<&'a ClientHint>,
+    pub os: Option<&'a OS>,
+    /// The client detected, for device parsers, clients are detected first.
+    pub client: Option<&'a Client>,
+}
+
+/// A client or device parser, a link in the chain of parsers tried in turn until one
+/// detects something.
+pub trait Parser: Send + Sync {
+    /// A [`Client`] or a [`Device`].
+    type Output;
+
+    /// Names the parser in traces, see [`TraceStep::Parser`](crate::trace::TraceStep::Parser).
+    fn name(&self) -> &str;
+
+    /// What the parser detects, or `None` to go on with the next parser.
+    fn parse(&self, input: &ParseInput<'_>) -> Result<Option<Self::Output>>;
+}
+
+/// Rewrites a detection once every parser and heuristic is done, before it is cached
+/// and returned. Bots are never post-processed.
+pub trait PostProcessor: Send + Sync {
+    fn process(
+        &self,
+        ua: &str,
+        client_hints: Option<&ClientHint>,
+        known: &mut KnownDevice,
+    ) -> Result<()>;
+}
+
+/// Where in a chain of parsers to insert one, see
+/// [`DeviceDetectorBuilder::insert_client_parser`]. A position relative to a parser of
+/// the other chain, eg. a device parser before [`ParserFamily::Browser`], is an error.
+///
+/// [`DeviceDetectorBuilder::insert_client_parser`]: crate::device_detector::DeviceDetectorBuilder::insert_client_parser
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Position {
+    First,
+    Last,
+    Before(ParserFamily),
+    After(ParserFamily),
+}
+
+/// One link of a chain of parsers.
+pub(crate) enum Stage<T> {
+    Builtin(ParserFamily),
+    Custom(Arc<dyn Parser<Output = T>>),
+}
+
+impl<T> Clone for Stage<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Stage::Builtin(family) => Stage::Builtin(*family),
+            Stage::Custom(parser) => Stage::Custom(parser.clone()),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Stage<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Builtin(family) => write!(f, "{:?}", family),
+            Stage::Custom(parser) => write!(f, "{:?}", parser.name()),
+        }
+    }
+}
+
+/// The chains of client and device parsers, in the order upstream tries them unless
+/// parsers were inserted, and the post-processors in the order they were added.
+#[derive(Clone)]
+pub(crate) struct Pipeline {
+    pub(crate) clients: Vec<Stage<Client>>,
+    pub(crate) devices: Vec<Stage<Device>>,
+    pub(crate) post_processors: Vec<Arc<dyn PostProcessor>>,
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        use ParserFamily::*;
+
+        Self {
+            clients: [FeedReader, MobileApp, MediaPlayer, Pim, Library, Browser]
+                .into_iter()
+                .map(Stage::Builtin)
+                .collect(),
+            devices: [
+                Television,
+                ShellTv,
+                Notebook,
+                Console,
+                CarBrowser,
+                Camera,
+                PortableMediaPlayer,
+                Mobile,
+            ]
+            .into_iter()
+            .map(Stage::Builtin)
+            .collect(),
+            post_processors: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("clients", &self.clients)
+            .field("devices", &self.devices)
+            .field("post_processors", &self.post_processors.len())
+            .finish()
+    }
+}
+
+/// Inserts a stage into a chain, failing if relative to a parser that isn't in it.
+pub(crate) fn insert<T>(
+    stages: &mut Vec<Stage<T>>,
+    position: Position,
+    stage: Stage<T>,
+) -> Result<()> {
+    let index = |family| {
+        stages
+            .iter()
+            .position(|stage| matches!(stage, Stage::Builtin(f) if *f == family))
+            .ok_or_else(|| {
+                DetectError::InvalidInput(format!(
+                    "can't insert a parser next to {:?}, which isn't one of {:?}",
+                    family, stages
+                ))
+            })
+    };
+
+    let at = match position {
+        Position::First => 0,
+        Position::Last => stages.len(),
+        Position::Before(family) => index(family)?,
+        Position::After(family) => index(family)? + 1,
+    };
+
+    stages.insert(at, stage);
+    Ok(())
+}
+
+/// Runs a parser of a chain, for traces noting it if it detected something.
+pub(crate) fn run<T>(
+    part: Part,
+    parser: &dyn Parser<Output = T>,
+    input: &ParseInput<'_>,
+) -> Result<Option<T>> {
+    let res = parser.parse(input)?;
+    if res.is_some() {
+        trace::parser(part, parser.name());
+    }
+    Ok(res)
+}
@@ -0,0 +1,656 @@
+use once_cell::sync::Lazy;
+use serde::de::DeserializeOwned;
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+use crate::error::{DetectError, Result};
+use crate::known_brands::AvailableBrands;
+use crate::parsers::bot::BotList;
+use crate::parsers::client::browsers::engines::BrowserEngineList;
+use crate::parsers::client::browsers::BrowserClientList;
+use crate::parsers::client::hints::HintList;
+use crate::parsers::client::ClientList;
+use crate::parsers::device::DeviceList;
+use crate::parsers::oss::OSList;
+use crate::parsers::utils::LazyRegex;
+use crate::parsers::vendor_fragments::VendorFragmentList;
+
+/// The contents of a single rule file.
+#[derive(Clone, Debug)]
+enum RuleSource {
+    Yaml(Cow<'static, str>),
+    /// Message pack pre-parsed from yaml by build.rs, deflated with the `compress` feature.
+    #[cfg(feature = "precompiled")]
+    Precompiled(&'static [u8]),
+}
+
+#[cfg(feature = "precompiled")]
+macro_rules! embedded_file {
+    ($file:literal) => {
+        RuleSource::Precompiled(include_bytes!(concat!(
+            env!("OUT_DIR"),
+            "/regexes/",
+            $file,
+            ".bin"
+        )))
+    };
+}
+
+#[cfg(not(feature = "precompiled"))]
+macro_rules! embedded_file {
+    ($file:literal) => {
+        RuleSource::Yaml(Cow::Borrowed(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/regexes/",
+            $file
+        ))))
+    };
+}
+
+macro_rules! embedded_files {
+    ($($file:literal),* $(,)?) => {
+        [$(($file, embedded_file!($file)),)*]
+    };
+}
+
+// Every file of the upstream `regexes/` directory that we make use of, relative to that
+// directory, along with the copy that was compiled into this binary.
+static EMBEDDED_FILES: [(&str, RuleSource); 20] = embedded_files![
+    "bots.yml",
+    "oss.yml",
+    "vendorfragments.yml",
+    "client/browser_engine.yml",
+    "client/browsers.yml",
+    "client/feed_readers.yml",
+    "client/libraries.yml",
+    "client/mediaplayers.yml",
+    "client/mobile_apps.yml",
+    "client/pim.yml",
+    "client/hints/apps.yml",
+    "client/hints/browsers.yml",
+    "device/cameras.yml",
+    "device/car_browsers.yml",
+    "device/consoles.yml",
+    "device/mobiles.yml",
+    "device/notebooks.yml",
+    "device/portable_media_player.yml",
+    "device/shell_tv.yml",
+    "device/televisions.yml",
+];
+
+/// A list of rules that overlays can be put ahead of.
+pub(crate) trait RuleList: DeserializeOwned {
+    /// How many rules the list has, as numbered by its `regexes`.
+    fn len(&self) -> usize;
+
+    /// Puts the rules of `overlay` ahead of the list's own.
+    fn prepend(&mut self, overlay: Self);
+}
+
+/// Extra rules in the same schema as one of the rule files, checked before the rules
+/// of that file.
+#[derive(Clone, Debug)]
+struct Overlay {
+    /// The rule file it extends, relative to the `regexes/` directory.
+    file: String,
+    /// Names the overlay in errors and traces.
+    name: String,
+    contents: String,
+}
+
+/// The raw yaml contents of a rule database, keyed by path relative to the
+/// `regexes/` directory, eg. `"device/mobiles.yml"`.
+#[derive(Clone, Debug)]
+pub(crate) struct RuleSources {
+    files: HashMap<String, RuleSource>,
+    overlays: Vec<Overlay>,
+}
+
+impl RuleSources {
+    /// The yaml files that were compiled into this binary.
+    pub(crate) fn embedded() -> Self {
+        let files = EMBEDDED_FILES
+            .iter()
+            .map(|(file, source)| ((*file).to_owned(), source.clone()))
+            .collect();
+
+        Self {
+            files,
+            overlays: Vec::new(),
+        }
+    }
+
+    /// Reads every rule file from a directory laid out like upstream's `regexes/`.
+    /// Every file must be present, a partial directory is almost certainly a
+    /// deployment mistake rather than something we should paper over.
+    pub(crate) fn from_dir(dir: &Path) -> Result<Self> {
+        let mut files = HashMap::with_capacity(EMBEDDED_FILES.len());
+
+        for (file, _) in EMBEDDED_FILES.iter() {
+            let path = dir.join(file);
+            let contents = std::fs::read_to_string(&path).map_err(|err| {
+                DetectError::rule_database(file, format!("reading {}: {}", path.display(), err))
+            })?;
+            files.insert((*file).to_owned(), RuleSource::Yaml(Cow::Owned(contents)));
+        }
+
+        Ok(Self {
+            files,
+            overlays: Vec::new(),
+        })
+    }
+
+    /// Replaces individual files, any file not supplied keeps its current contents.
+    pub(crate) fn with_overrides<I, K, V>(mut self, sources: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        for (file, contents) in sources {
+            let file = file.into();
+            if !self.files.contains_key(&file) {
+                return Err(DetectError::rule_database(&file, "unknown rule file"));
+            }
+            self.files
+                .insert(file, RuleSource::Yaml(Cow::Owned(contents.into())));
+        }
+
+        Ok(self)
+    }
+
+    /// Adds rules to be checked before those of `file`, after any overlay of the same
+    /// file added before.
+    pub(crate) fn with_overlay(mut self, file: &str, name: &str, contents: String) -> Result<Self> {
+        if !self.files.contains_key(file) {
+            return Err(DetectError::rule_database(file, "unknown rule file"));
+        }
+        // hints map names to names rather than being rules tried in order.
+        if file.starts_with("client/hints/") {
+            return Err(DetectError::rule_database(
+                file,
+                "overlays are only supported for files of rules",
+            ));
+        }
+
+        self.overlays.push(Overlay {
+            file: file.to_owned(),
+            name: name.to_owned(),
+            contents,
+        });
+        Ok(self)
+    }
+
+    /// Loads `file` with its overlays ahead of it, noting how many rules each added.
+    fn load_rules<T: RuleList>(&self, file: &str, overlays: &mut Vec<OverlayRules>) -> Result<T> {
+        let mut rules: T = self.load(file)?;
+
+        let mut lists = Vec::new();
+        for overlay in self.overlays.iter().filter(|overlay| overlay.file == file) {
+            let list: T = serde_yaml::from_str(&overlay.contents)
+                .map_err(|err| DetectError::rule_database(&overlay.name, err))?;
+            overlays.push(OverlayRules {
+                file: overlay.file.clone(),
+                name: overlay.name.clone(),
+                rules: list.len(),
+            });
+            lists.push(list);
+        }
+
+        // the first overlay added ends up first.
+        for list in lists.into_iter().rev() {
+            rules.prepend(list);
+        }
+
+        Ok(rules)
+    }
+
+    fn load<T: DeserializeOwned>(&self, file: &str) -> Result<T> {
+        let error = |message| DetectError::rule_database(file, message);
+
+        let source = self
+            .files
+            .get(file)
+            .ok_or_else(|| error("missing rule file".to_owned()))?;
+
+        let res = match source {
+            RuleSource::Yaml(contents) => {
+                serde_yaml::from_str(contents).map_err(|err| error(err.to_string()))?
+            }
+
+            #[cfg(all(feature = "precompiled", not(feature = "compress")))]
+            RuleSource::Precompiled(bytes) => {
+                rmp_serde::from_slice(bytes).map_err(|err| error(err.to_string()))?
+            }
+
+            #[cfg(feature = "compress")]
+            RuleSource::Precompiled(bytes) => {
+                let bytes = miniz_oxide::inflate::decompress_to_vec(bytes)
+                    .map_err(|err| error(format!("inflating: {}", err)))?;
+                rmp_serde::from_slice(&bytes).map_err(|err| error(err.to_string()))?
+            }
+        };
+
+        Ok(res)
+    }
+}
+
+/// Every parsed rule list needed to detect a user agent.
+///
+/// A database is an ordinary value, so several versions can be loaded side by side,
+/// eg. to compare a newer upstream release against the current one, and each is
+/// freed once the last detector using it is dropped.
+#[derive(Debug)]
+pub struct RuleDatabase {
+    pub(crate) bots: BotList,
+    pub(crate) oss: OSList,
+    pub(crate) vendor_fragments: VendorFragmentList,
+
+    pub(crate) browser_engines: BrowserEngineList,
+    pub(crate) browsers: BrowserClientList,
+    pub(crate) feed_readers: ClientList,
+    pub(crate) libraries: ClientList,
+    pub(crate) media_players: ClientList,
+    pub(crate) mobile_apps: ClientList,
+    pub(crate) pims: ClientList,
+    pub(crate) app_hints: HintList,
+    pub(crate) browser_hints: HintList,
+
+    pub(crate) cameras: DeviceList,
+    pub(crate) car_browsers: DeviceList,
+    pub(crate) consoles: DeviceList,
+    pub(crate) mobiles: DeviceList,
+    pub(crate) notebooks: DeviceList,
+    pub(crate) portable_media_players: DeviceList,
+    pub(crate) shell_tvs: DeviceList,
+    pub(crate) televisions: DeviceList,
+
+    // in the order their rules sit ahead of those of their file.
+    overlays: Vec<OverlayRules>,
+
+    // unique to this database, so cached detections made with it can't be mistaken
+    // for those of another database sharing the cache.
+    pub(crate) generation: u64,
+}
+
+// The rules an overlay added to one of the lists.
+#[derive(Debug)]
+struct OverlayRules {
+    file: String,
+    name: String,
+    rules: usize,
+}
+
+/// A rule only the backtracking regex engine can run, see
+/// [`RuleDatabase::backtracking_rules`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BacktrackingRule {
+    /// The rule file, relative to the `regexes/` directory.
+    pub file: &'static str,
+    /// The overlay the rule came from, if it isn't one of the file's own.
+    pub overlay: Option<String>,
+    /// Index of the rule within that file, or within the overlay.
+    pub rule: usize,
+    /// The regex as compiled, including any wrapping added to the rule's own.
+    pub pattern: String,
+}
+
+/// A brand the device rules name that isn't one of upstream's, see
+/// [`RuleDatabase::unknown_brands`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownBrand {
+    /// The rule file, relative to the `regexes/` directory.
+    pub file: &'static str,
+    /// The overlay the rule came from, if it isn't one of the file's own.
+    pub overlay: Option<String>,
+    /// Index of the rule within that file, or within the overlay.
+    pub rule: usize,
+    pub brand: String,
+}
+
+// Only a weak reference is kept so that the embedded database is freed along with
+// the last detector using it, but it is never parsed twice while one is alive.
+static EMBEDDED_DATABASE: Lazy<Mutex<Weak<RuleDatabase>>> = Lazy::new(|| Mutex::new(Weak::new()));
+
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+impl RuleDatabase {
+    /// The database compiled into this binary, shared with any other detector
+    /// currently using it.
+    pub fn embedded() -> Arc<RuleDatabase> {
+        let mut embedded = EMBEDDED_DATABASE.lock().unwrap();
+
+        if let Some(database) = embedded.upgrade() {
+            return database;
+        }
+
+        let database = Arc::new(
+            RuleDatabase::load(&RuleSources::embedded()).expect("loading embedded rule database"),
+        );
+        *embedded = Arc::downgrade(&database);
+        database
+    }
+
+    /// Reads a database from a directory laid out like upstream's `regexes/`
+    /// directory, eg. a newer checkout of matomo's rules.
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<RuleDatabase> {
+        RuleDatabase::load(&RuleSources::from_dir(dir.as_ref())?)
+    }
+
+    /// Builds a database from in memory yaml, keyed by path relative to the `regexes/`
+    /// directory, eg. `("device/mobiles.yml", contents)`. Any file not supplied falls
+    /// back to the copy compiled into this binary.
+    pub fn from_sources<I, K, V>(sources: I) -> Result<RuleDatabase>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        RuleDatabase::load(&RuleSources::embedded().with_overrides(sources)?)
+    }
+
+    /// Builds a database from the embedded rules or a directory, replacing individual
+    /// files or adding overlays of extra rules.
+    pub fn builder() -> RuleDatabaseBuilder {
+        RuleDatabaseBuilder::default()
+    }
+
+    /// The bots of the database, to list them or check names against them, eg. for a
+    /// list of bots to allow.
+    pub fn bots(&self) -> &BotList {
+        &self.bots
+    }
+
+    /// Compiles every regex up front. Regexes are otherwise compiled on first use, so
+    /// a database that loaded fine may still contain a rule that fails later on.
+    pub fn validate(&self) -> Result<()> {
+        for (file, rule, regex) in self.regexes() {
+            regex
+                .compile()
+                .map_err(|err| self.in_rule(err, file, rule))?;
+        }
+        Ok(())
+    }
+
+    /// Adds the file and rule to an error compiling one of our regexes on first use.
+    pub(crate) fn locate(&self, err: DetectError) -> DetectError {
+        let failed = match &err {
+            DetectError::RuleDatabase {
+                file,
+                pattern: Some(pattern),
+                ..
+            } if file.is_empty() => pattern,
+            _ => return err,
+        };
+
+        match self
+            .regexes()
+            .find(|(_, _, regex)| regex.pattern == *failed)
+        {
+            Some((file, rule, _)) => self.in_rule(err, file, rule),
+            None => err,
+        }
+    }
+
+    // Adds the rule to an error, by its index within the overlay it came from if any.
+    fn in_rule(&self, err: DetectError, file: &str, rule: usize) -> DetectError {
+        match self.origin(file, rule) {
+            (Some(overlay), rule) => err.in_rule(overlay, rule),
+            (None, rule) => err.in_rule(file, rule),
+        }
+    }
+
+    // The overlay a rule of `file`, as numbered with overlays ahead of the file's own
+    // rules, came from, if any, and its index within the overlay or the file.
+    fn origin(&self, file: &str, mut rule: usize) -> (Option<&str>, usize) {
+        for overlay in self.overlays.iter().filter(|overlay| overlay.file == file) {
+            if rule < overlay.rules {
+                return (Some(&overlay.name), rule);
+            }
+            rule -= overlay.rules;
+        }
+        (None, rule)
+    }
+
+    /// Every rule whose regex can't be run by the linear time engine, and so falls
+    /// back to the backtracking one, which may give up on a match for some user
+    /// agents, failing their detection with [`DetectError::RegexLimit`]. Compiles the whole database, like [`Self::validate`].
+    pub fn backtracking_rules(&self) -> Result<Vec<BacktrackingRule>> {
+        self.validate()?;
+
+        let mut rules = Vec::new();
+        for (file, rule, regex) in self.regexes() {
+            if regex.compile()?.needs_backtracking() {
+                let (overlay, rule) = self.origin(file, rule);
+                rules.push(BacktrackingRule {
+                    file,
+                    overlay: overlay.map(str::to_owned),
+                    rule,
+                    pattern: regex.pattern.clone(),
+                });
+            }
+        }
+        Ok(rules)
+    }
+
+    /// Every brand named by the device rules or vendor fragments that isn't in
+    /// [`AvailableBrands`], eg. a typo in an overlay, or a brand newer rules added.
+    /// Brands differing from a known one only in case or spaces are reported too, as
+    /// detections give the known spelling instead.
+    pub fn unknown_brands(&self) -> Vec<UnknownBrand> {
+        let available = AvailableBrands::default();
+        let devices = [
+            ("device/cameras.yml", &self.cameras),
+            ("device/car_browsers.yml", &self.car_browsers),
+            ("device/consoles.yml", &self.consoles),
+            ("device/mobiles.yml", &self.mobiles),
+            ("device/notebooks.yml", &self.notebooks),
+            (
+                "device/portable_media_player.yml",
+                &self.portable_media_players,
+            ),
+            ("device/shell_tv.yml", &self.shell_tvs),
+            ("device/televisions.yml", &self.televisions),
+        ];
+
+        let brands = devices
+            .into_iter()
+            .flat_map(|(file, list)| list.brands().map(move |(rule, brand)| (file, rule, brand)))
+            .chain(
+                self.vendor_fragments
+                    .brands()
+                    .map(|(rule, brand)| ("vendorfragments.yml", rule, brand)),
+            );
+
+        let mut unknown = Vec::new();
+        for (file, rule, brand) in brands {
+            let known = available
+                .search_by_name(brand)
+                .is_some_and(|known| known.name == brand);
+            if !known {
+                let (overlay, rule) = self.origin(file, rule);
+                unknown.push(UnknownBrand {
+                    file,
+                    overlay: overlay.map(str::to_owned),
+                    rule,
+                    brand: brand.to_owned(),
+                });
+            }
+        }
+        // models of a rule may name the same brand over and over.
+        unknown.dedup();
+        unknown
+    }
+
+    /// The file, overlay and rule index of one of our regexes, for explaining a
+    /// detection.
+    pub(crate) fn locate_regex(
+        &self,
+        regex: *const LazyRegex,
+    ) -> Option<(&'static str, Option<&str>, usize)> {
+        self.regexes()
+            .find(|(_, _, candidate)| std::ptr::eq(*candidate, regex))
+            .map(|(file, rule, _)| {
+                let (overlay, rule) = self.origin(file, rule);
+                (file, overlay, rule)
+            })
+    }
+
+    // Every regex of the database, along with its file and the index of its rule, rules
+    // of overlays numbered ahead of the file's own.
+    fn regexes(&self) -> impl Iterator<Item = (&'static str, usize, &LazyRegex)> {
+        fn file<'a>(
+            file: &'static str,
+            regexes: impl Iterator<Item = (usize, &'a LazyRegex)> + 'a,
+        ) -> impl Iterator<Item = (&'static str, usize, &'a LazyRegex)> + 'a {
+            regexes.map(move |(rule, regex)| (file, rule, regex))
+        }
+
+        file("bots.yml", self.bots.regexes())
+            .chain(file("oss.yml", self.oss.regexes()))
+            .chain(file("vendorfragments.yml", self.vendor_fragments.regexes()))
+            .chain(file(
+                "client/browser_engine.yml",
+                self.browser_engines.regexes(),
+            ))
+            .chain(file("client/browsers.yml", self.browsers.regexes()))
+            .chain(file("client/feed_readers.yml", self.feed_readers.regexes()))
+            .chain(file("client/libraries.yml", self.libraries.regexes()))
+            .chain(file(
+                "client/mediaplayers.yml",
+                self.media_players.regexes(),
+            ))
+            .chain(file("client/mobile_apps.yml", self.mobile_apps.regexes()))
+            .chain(file("client/pim.yml", self.pims.regexes()))
+            .chain(file("device/cameras.yml", self.cameras.regexes()))
+            .chain(file("device/car_browsers.yml", self.car_browsers.regexes()))
+            .chain(file("device/consoles.yml", self.consoles.regexes()))
+            .chain(file("device/mobiles.yml", self.mobiles.regexes()))
+            .chain(file("device/notebooks.yml", self.notebooks.regexes()))
+            .chain(file(
+                "device/portable_media_player.yml",
+                self.portable_media_players.regexes(),
+            ))
+            .chain(file("device/shell_tv.yml", self.shell_tvs.regexes()))
+            .chain(file("device/televisions.yml", self.televisions.regexes()))
+    }
+
+    pub(crate) fn load(sources: &RuleSources) -> Result<Self> {
+        let mut overlays = Vec::new();
+
+        Ok(Self {
+            bots: sources.load_rules("bots.yml", &mut overlays)?,
+            oss: sources.load_rules("oss.yml", &mut overlays)?,
+            vendor_fragments: sources.load_rules("vendorfragments.yml", &mut overlays)?,
+
+            browser_engines: sources.load_rules("client/browser_engine.yml", &mut overlays)?,
+            browsers: sources.load_rules("client/browsers.yml", &mut overlays)?,
+            feed_readers: sources.load_rules("client/feed_readers.yml", &mut overlays)?,
+            libraries: sources.load_rules("client/libraries.yml", &mut overlays)?,
+            media_players: sources.load_rules("client/mediaplayers.yml", &mut overlays)?,
+            mobile_apps: sources.load_rules("client/mobile_apps.yml", &mut overlays)?,
+            pims: sources.load_rules("client/pim.yml", &mut overlays)?,
+            app_hints: sources.load("client/hints/apps.yml")?,
+            browser_hints: sources.load("client/hints/browsers.yml")?,
+
+            cameras: sources.load_rules("device/cameras.yml", &mut overlays)?,
+            car_browsers: sources.load_rules("device/car_browsers.yml", &mut overlays)?,
+            consoles: sources.load_rules("device/consoles.yml", &mut overlays)?,
+            mobiles: sources.load_rules("device/mobiles.yml", &mut overlays)?,
+            notebooks: sources.load_rules("device/notebooks.yml", &mut overlays)?,
+            portable_media_players: sources
+                .load_rules("device/portable_media_player.yml", &mut overlays)?,
+            shell_tvs: sources.load_rules("device/shell_tv.yml", &mut overlays)?,
+            televisions: sources.load_rules("device/televisions.yml", &mut overlays)?,
+
+            overlays,
+            generation: NEXT_GENERATION.fetch_add(1, Ordering::Relaxed),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+enum OverlaySource {
+    Yaml(String),
+    Path(PathBuf),
+}
+
+/// Builds a [`RuleDatabase`], see [`RuleDatabase::builder`].
+///
+/// Sources are only read by [`build`](Self::build), which can be called again to pick
+/// up changes to the files, eg. on reload.
+#[derive(Clone, Debug, Default)]
+pub struct RuleDatabaseBuilder {
+    dir: Option<PathBuf>,
+    sources: Vec<(String, String)>,
+    overlays: Vec<(String, String, OverlaySource)>,
+}
+
+impl RuleDatabaseBuilder {
+    /// Reads the rules from a directory laid out like upstream's `regexes/` directory,
+    /// rather than using those compiled into this binary.
+    pub fn dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// Replaces a single rule file, by path relative to the `regexes/` directory.
+    pub fn source(mut self, file: impl Into<String>, contents: impl Into<String>) -> Self {
+        self.sources.push((file.into(), contents.into()));
+        self
+    }
+
+    /// Adds rules in the same schema as `file`, eg. `"bots.yml"` or
+    /// `"device/mobiles.yml"`, to be checked before the file's own rules. Overlays of
+    /// the same file are checked in the order they were added. `name` identifies the
+    /// overlay in errors and in [`TraceStep`](crate::trace::TraceStep)s.
+    pub fn overlay(
+        mut self,
+        file: impl Into<String>,
+        name: impl Into<String>,
+        contents: impl Into<String>,
+    ) -> Self {
+        self.overlays.push((
+            file.into(),
+            name.into(),
+            OverlaySource::Yaml(contents.into()),
+        ));
+        self
+    }
+
+    /// Like [`overlay`](Self::overlay), reading the rules from `path`, which also
+    /// names the overlay.
+    pub fn overlay_file(mut self, file: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        self.overlays.push((
+            file.into(),
+            path.display().to_string(),
+            OverlaySource::Path(path),
+        ));
+        self
+    }
+
+    pub fn build(&self) -> Result<RuleDatabase> {
+        let sources = match &self.dir {
+            Some(dir) => RuleSources::from_dir(dir)?,
+            None => RuleSources::embedded(),
+        };
+        let mut sources = sources.with_overrides(self.sources.iter().cloned())?;
+
+        for (file, name, source) in self.overlays.iter() {
+            let contents = match source {
+                OverlaySource::Yaml(contents) => contents.clone(),
+                OverlaySource::Path(path) => std::fs::read_to_string(path).map_err(|err| {
+                    DetectError::rule_database(name, format!("reading {}: {}", path.display(), err))
+                })?,
+            };
+            sources = sources.with_overlay(file, name, contents)?;
+        }
+
+        RuleDatabase::load(&sources)
+    }
+}
@@ -0,0 +1,438 @@
+//! Explaining a detection, which rule produced each part of it and what changed it
+//! afterwards, see [`DeviceDetector::explain`](crate::device_detector::DeviceDetector::explain).
+//!
+//! Steps are recorded per thread, and only while a detection is being explained, so
+//! ordinary detections pay for nothing more than checking whether to record.
+
+use serde::Serialize;
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use crate::device_detector::{Detection, KnownDevice};
+use crate::error::Result;
+use crate::parsers::client::Client;
+use crate::parsers::device::Device;
+use crate::parsers::oss::OS;
+use crate::parsers::utils::LazyRegex;
+use crate::rule_database::RuleDatabase;
+
+/// The part of a detection a step is about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Part {
+    Bot,
+    Os,
+    Client,
+    Engine,
+    Device,
+}
+
+/// A single step of a detection, in the order it happened.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "step", rename_all = "snake_case")]
+pub enum TraceStep {
+    /// A rule matched.
+    Rule {
+        part: Part,
+        /// The rule file, relative to the `regexes/` directory.
+        file: String,
+        /// The overlay of `file` the rule came from, if it isn't one of the file's own,
+        /// see [`RuleDatabaseBuilder::overlay`](crate::rule_database::RuleDatabaseBuilder::overlay).
+        overlay: Option<String>,
+        /// Index of the rule within that file, or within the overlay.
+        rule: usize,
+        /// For device rules, the index of the matching entry of the rule's `models`,
+        /// a lone `model` counting as the first.
+        model: Option<usize>,
+    },
+
+    /// A parser of your own detected the part, see [`Parser`](crate::pipeline::Parser).
+    Parser { part: Part, name: String },
+
+    /// A field set or changed other than by a rule, eg. from client hints, or by one
+    /// of the heuristics applied once the rules are done.
+    Override {
+        part: Part,
+        field: &'static str,
+        /// The value after the change.
+        value: Option<String>,
+        reason: Reason,
+    },
+}
+
+/// Why a field was set or changed other than by a rule, see [`TraceStep::Override`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reason {
+    AndroidHeuristics,
+    AppleOs,
+    BrandNormalization,
+    ClientHints,
+    DesktopHeuristics,
+    OsHeuristics,
+    /// Upstream's fixes to a client or os once detected, eg. Fire OS for the Android
+    /// of Amazon's devices.
+    PostProcessing,
+    /// A post-processor of your own, see [`pipeline`](crate::pipeline).
+    PostProcessor,
+    PuffinHeuristics,
+    TvHeuristics,
+    VendorFragment,
+    VersionTruncation,
+}
+
+impl Reason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AndroidHeuristics => "android heuristics",
+            Self::AppleOs => "apple os",
+            Self::BrandNormalization => "brand normalization",
+            Self::ClientHints => "client hints",
+            Self::DesktopHeuristics => "desktop heuristics",
+            Self::OsHeuristics => "os heuristics",
+            Self::PostProcessing => "post-processing",
+            Self::PostProcessor => "post-processor",
+            Self::PuffinHeuristics => "puffin heuristics",
+            Self::TvHeuristics => "tv heuristics",
+            Self::VendorFragment => "vendor fragment",
+            Self::VersionTruncation => "version truncation",
+        }
+    }
+
+    /// Where a field changed for this reason came from, `None` for changes that
+    /// leave it where it was, eg. truncating a version.
+    fn source(&self) -> Option<Source> {
+        match self {
+            Self::VersionTruncation | Self::BrandNormalization => None,
+            Self::ClientHints => Some(Source::ClientHints),
+            Self::VendorFragment => Some(Source::VendorFragment),
+            Self::PostProcessor => Some(Source::Custom),
+            Self::AndroidHeuristics
+            | Self::AppleOs
+            | Self::DesktopHeuristics
+            | Self::OsHeuristics
+            | Self::PostProcessing
+            | Self::PuffinHeuristics
+            | Self::TvHeuristics => Some(Source::Heuristic),
+        }
+    }
+}
+
+impl Serialize for Reason {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// A detection along with how it came about, see
+/// [`DeviceDetector::explain`](crate::device_detector::DeviceDetector::explain).
+#[derive(Clone, Debug)]
+pub struct Explanation {
+    pub detection: Detection,
+    pub trace: Vec<TraceStep>,
+}
+
+impl Explanation {
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::json!({
+            "detection": self.detection.clone().to_value(),
+            "trace": self.trace,
+        })
+    }
+}
+
+/// Where the value of a field of a detection came from, see [`Provenance`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    /// A rule matching the user agent.
+    Ua,
+    /// The client hints, including rules matching a user agent the hints restored,
+    /// eg. the model of a reduced `Android 10; K` user agent.
+    ClientHints,
+    /// One of upstream's heuristics applied once the rules are done.
+    Heuristic,
+    /// The brand of a vendor fragment, eg. `MAAR` for Acer.
+    VendorFragment,
+    /// A parser or post-processor of your own, see [`pipeline`](crate::pipeline).
+    Custom,
+}
+
+/// Where each field of a detection came from, by field as named in the trace, see
+/// [`DeviceDetectorBuilder::record_provenance`].
+///
+/// [`DeviceDetectorBuilder::record_provenance`]: crate::device_detector::DeviceDetectorBuilder::record_provenance
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Provenance {
+    pub client: BTreeMap<&'static str, Source>,
+    pub device: BTreeMap<&'static str, Source>,
+    pub os: BTreeMap<&'static str, Source>,
+}
+
+impl Provenance {
+    fn from_recorded(recorded: &[Recorded], known: &KnownDevice) -> Self {
+        let source = |part: Part, field: &str| {
+            let changed = recorded.iter().rev().find_map(|step| match step {
+                Recorded::Step(TraceStep::Override {
+                    part: p,
+                    field: f,
+                    reason,
+                    ..
+                }) if *p == part && *f == field => reason.source(),
+                _ => None,
+            });
+
+            let custom = recorded.iter().any(|step| {
+                matches!(step, Recorded::Step(TraceStep::Parser { part: p, .. }) if *p == part)
+            });
+
+            changed.unwrap_or(if custom { Source::Custom } else { Source::Ua })
+        };
+
+        fn sources<T: Traced>(
+            value: Option<&T>,
+            source: impl Fn(Part, &str) -> Source,
+        ) -> BTreeMap<&'static str, Source> {
+            T::FIELDS
+                .iter()
+                .zip(fields(value))
+                .filter(|(_, value)| value.is_some())
+                .map(|(field, _)| (*field, source(T::PART, field)))
+                .collect()
+        }
+
+        Self {
+            client: sources(known.client.as_ref(), source),
+            device: sources(known.device.as_ref(), source),
+            os: sources(known.os.as_ref(), source),
+        }
+    }
+}
+
+// Steps as recorded, rules are resolved to their file and index afterwards, which
+// spares the rule lists from knowing which file they came from.
+enum Recorded {
+    Rule {
+        part: Part,
+        regex: *const LazyRegex,
+        model: Option<usize>,
+    },
+    Step(TraceStep),
+}
+
+thread_local! {
+    static TRACE: RefCell<Option<Vec<Recorded>>> = const { RefCell::new(None) };
+}
+
+// Swaps the trace of this thread for another until finished or dropped, so that the
+// previous one is restored even if detecting panics.
+struct Swap {
+    previous: Option<Option<Vec<Recorded>>>,
+}
+
+impl Swap {
+    fn new(trace: Option<Vec<Recorded>>) -> Self {
+        Swap {
+            previous: Some(TRACE.with(|current| current.replace(trace))),
+        }
+    }
+
+    /// Restores the previous trace, returning the steps recorded meanwhile.
+    fn finish(mut self) -> Vec<Recorded> {
+        self.restore().unwrap_or_default()
+    }
+
+    fn restore(&mut self) -> Option<Vec<Recorded>> {
+        let previous = self.previous.take()?;
+        TRACE.with(|current| current.replace(previous))
+    }
+}
+
+impl Drop for Swap {
+    fn drop(&mut self) {
+        self.restore();
+    }
+}
+
+fn record(step: impl FnOnce() -> Recorded) {
+    TRACE.with(|trace| {
+        if let Some(trace) = trace.borrow_mut().as_mut() {
+            trace.push(step());
+        }
+    });
+}
+
+/// Records every step `detect` takes on this thread.
+pub(crate) fn explain<T>(
+    database: &RuleDatabase,
+    detect: impl FnOnce() -> T,
+) -> (T, Vec<TraceStep>) {
+    let swap = Swap::new(Some(Vec::new()));
+    let res = detect();
+    let recorded = swap.finish();
+
+    let trace = recorded
+        .into_iter()
+        .filter_map(|step| match step {
+            Recorded::Step(step) => Some(step),
+            Recorded::Rule { part, regex, model } => {
+                let (file, overlay, rule) = database.locate_regex(regex)?;
+                Some(TraceStep::Rule {
+                    part,
+                    file: file.to_owned(),
+                    overlay: overlay.map(str::to_owned),
+                    rule,
+                    model,
+                })
+            }
+        })
+        .collect();
+
+    (res, trace)
+}
+
+/// Runs `detect` recording its steps, for the provenance of the device it detects.
+/// The steps are passed on to any explanation under way.
+pub(crate) fn provenance(detect: impl FnOnce() -> Result<Detection>) -> Result<Detection> {
+    let swap = Swap::new(Some(Vec::new()));
+    let mut res = detect();
+    let recorded = swap.finish();
+
+    if let Ok(Detection::Known(known)) = &mut res {
+        known.provenance = Some(Provenance::from_recorded(&recorded, known));
+    }
+
+    TRACE.with(|trace| {
+        if let Some(trace) = trace.borrow_mut().as_mut() {
+            trace.extend(recorded);
+        }
+    });
+
+    res
+}
+
+/// Runs `f` without recording its steps, for lookups that aren't part of the
+/// detection being explained.
+pub(crate) fn untraced<T>(f: impl FnOnce() -> T) -> T {
+    let _swap = Swap::new(None);
+    f()
+}
+
+/// Records that the rule of `regex` matched.
+pub(crate) fn rule(part: Part, regex: &LazyRegex) {
+    record(|| Recorded::Rule {
+        part,
+        regex,
+        model: None,
+    });
+}
+
+/// Records that the rule of `regex` matched, along with one of its models.
+pub(crate) fn model_rule(part: Part, regex: &LazyRegex, model: Option<usize>) {
+    record(|| Recorded::Rule { part, regex, model });
+}
+
+/// Records that a parser of your own detected a part.
+pub(crate) fn parser(part: Part, name: &str) {
+    record(|| {
+        Recorded::Step(TraceStep::Parser {
+            part,
+            name: name.to_owned(),
+        })
+    });
+}
+
+/// Fields of a part of a detection that a trace follows changes of.
+pub(crate) trait Traced {
+    const PART: Part;
+    const FIELDS: &'static [&'static str];
+
+    fn field(&self, field: &str) -> Option<String>;
+}
+
+/// The fields of a part before a change, when explaining.
+pub(crate) struct Snapshot(Option<Vec<Option<String>>>);
+
+fn fields<T: Traced>(value: Option<&T>) -> Vec<Option<String>> {
+    T::FIELDS
+        .iter()
+        .map(|field| value.and_then(|value| value.field(field)))
+        .collect()
+}
+
+pub(crate) fn snapshot<T: Traced>(value: Option<&T>) -> Snapshot {
+    let active = TRACE.with(|trace| trace.borrow().is_some());
+    Snapshot(active.then(|| fields(value)))
+}
+
+/// Records every field that changed since `before`.
+pub(crate) fn changes<T: Traced>(before: Snapshot, value: Option<&T>, reason: Reason) {
+    let Some(before) = before.0 else {
+        return;
+    };
+
+    for ((field, before), after) in T::FIELDS.iter().zip(before).zip(fields(value)) {
+        if before != after {
+            record(|| {
+                Recorded::Step(TraceStep::Override {
+                    part: T::PART,
+                    field,
+                    value: after,
+                    reason,
+                })
+            });
+        }
+    }
+}
+
+impl Traced for OS {
+    const PART: Part = Part::Os;
+    const FIELDS: &'static [&'static str] = &["name", "version", "platform", "family"];
+
+    fn field(&self, field: &str) -> Option<String> {
+        match field {
+            "name" => Some(self.name.clone()),
+            "version" => self.version.clone(),
+            "platform" => self.platform.clone(),
+            "family" => self.family.clone(),
+            _ => None,
+        }
+    }
+}
+
+impl Traced for Client {
+    const PART: Part = Part::Client;
+    const FIELDS: &'static [&'static str] =
+        &["name", "version", "type", "engine", "engine_version"];
+
+    fn field(&self, field: &str) -> Option<String> {
+        match field {
+            "name" => Some(self.name.clone()),
+            "version" => self.version.clone(),
+            "type" => Some(self.r#type.as_str().to_owned()),
+            "engine" => self.engine.clone(),
+            "engine_version" => self.engine_version.clone(),
+            _ => None,
+        }
+    }
+}
+
+impl Traced for Device {
+    const PART: Part = Part::Device;
+    const FIELDS: &'static [&'static str] = &["type", "brand", "model"];
+
+    fn field(&self, field: &str) -> Option<String> {
+        match field {
+            "type" => self
+                .device_type
+                .as_ref()
+                .map(|device_type| device_type.as_str().to_owned()),
+            "brand" => self.brand.clone(),
+            "model" => self.model.clone(),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//! Checking that a bot is who its user agent claims, as anyone can claim to be
+//! Googlebot, see [`DeviceDetectorBuilder::verify_bots`].
+//!
+//! [`DeviceDetectorBuilder::verify_bots`]: crate::device_detector::DeviceDetectorBuilder::verify_bots
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{DetectError, Result};
+use crate::parsers::bot::Bot;
+
+/// Whether a bot was found to come from where its operator says it crawls from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verification {
+    Verified,
+    /// The bot is one a verifier knows of, but the client isn't where it crawls from.
+    Spoofed,
+    /// Not verified either way, eg. no verifier knows of the bot, or there was no ip.
+    #[default]
+    Unknown,
+}
+
+/// Checks a bot detected from a user agent against the ip of the client sending it.
+pub trait BotVerifier: Send + Sync {
+    /// [`Verification::Unknown`] for bots the verifier knows nothing of. `ua` is the user
+    /// agent `bot` was detected from, as one rule may match several of an operator's
+    /// crawlers.
+    fn verify(&self, bot: &Bot, ua: &str, ip: IpAddr) -> Result<Verification>;
+}
+
+/// Verifies bots by the ip ranges their operators publish, in the json format shared
+/// by Google (`googlebot.json`), Bing (`bingbot.json`) and OpenAI (`gptbot.json`).
+///
+/// Each range file holds for the user agents of a bot naming a token, eg. Googlebot's
+/// rule also matches `AdsBot-Google`, which crawls from Google's special crawler ranges
+/// rather than those of `googlebot.json`. User agents of a bot naming no token ranges
+/// were given for are left unknown.
+#[derive(Clone, Debug, Default)]
+pub struct IpRanges {
+    // by bot name, then by lowercase token, as user agents don't agree on the case, eg.
+    // `bingbot` of BingBot.
+    ranges: HashMap<String, HashMap<String, Vec<IpRange>>>,
+}
+
+impl IpRanges {
+    /// Adds the ranges of `json` to the bot named `bot`, eg. `Googlebot`, for its user
+    /// agents naming the bot itself.
+    pub fn with_ranges(self, bot: impl Into<String>, json: &str) -> Result<Self> {
+        let bot = bot.into();
+        let token = bot.clone();
+        self.with_token_ranges(bot, token, json)
+    }
+
+    /// Adds the ranges of `json` to the bot named `bot` for its user agents containing
+    /// `token`, ignoring case, eg. Google's `special-crawlers.json` for the
+    /// `AdsBot-Google` user agents of `Googlebot`.
+    pub fn with_token_ranges(
+        mut self,
+        bot: impl Into<String>,
+        token: impl Into<String>,
+        json: &str,
+    ) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct RangeFile {
+            prefixes: Vec<Prefix>,
+        }
+
+        #[derive(Deserialize)]
+        struct Prefix {
+            #[serde(rename = "ipv4Prefix")]
+            ipv4: Option<String>,
+            #[serde(rename = "ipv6Prefix")]
+            ipv6: Option<String>,
+        }
+
+        let file: RangeFile = serde_json::from_str(json)
+            .map_err(|err| DetectError::InvalidInput(format!("invalid ip ranges: {}", err)))?;
+
+        let ranges = self
+            .ranges
+            .entry(bot.into())
+            .or_default()
+            .entry(token.into().to_lowercase())
+            .or_default();
+        for prefix in file.prefixes {
+            for range in prefix.ipv4.iter().chain(prefix.ipv6.iter()) {
+                ranges.push(IpRange::parse(range)?);
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Adds the ranges of the json file at `path`, see [`Self::with_ranges`].
+    pub fn with_ranges_file(self, bot: impl Into<String>, path: impl AsRef<Path>) -> Result<Self> {
+        let bot = bot.into();
+        let token = bot.clone();
+        self.with_token_ranges_file(bot, token, path)
+    }
+
+    /// Adds the ranges of the json file at `path`, see [`Self::with_token_ranges`].
+    pub fn with_token_ranges_file(
+        self,
+        bot: impl Into<String>,
+        token: impl Into<String>,
+        path: impl AsRef<Path>,
+    ) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|err| {
+            DetectError::InvalidInput(format!("unable to read {}: {}", path.display(), err))
+        })?;
+        self.with_token_ranges(bot, token, &json)
+    }
+}
+
+impl BotVerifier for IpRanges {
+    fn verify(&self, bot: &Bot, ua: &str, ip: IpAddr) -> Result<Verification> {
+        let Some(tokens) = self.ranges.get(&bot.name) else {
+            return Ok(Verification::Unknown);
+        };
+
+        let ua = ua.to_lowercase();
+        let ranges: Vec<&IpRange> = tokens
+            .iter()
+            .filter(|(token, _)| ua.contains(token.as_str()))
+            .flat_map(|(_, ranges)| ranges)
+            .collect();
+
+        let verification = if ranges.is_empty() {
+            Verification::Unknown
+        } else if ranges.iter().any(|range| range.contains(ip)) {
+            Verification::Verified
+        } else {
+            Verification::Spoofed
+        };
+        Ok(verification)
+    }
+}
+
+// A cidr block, eg. `66.249.64.0/27`.
+#[derive(Clone, Copy, Debug)]
+struct IpRange {
+    network: IpAddr,
+    prefix: u32,
+}
+
+impl IpRange {
+    fn parse(range: &str) -> Result<Self> {
+        let invalid = || DetectError::InvalidInput(format!("invalid ip range: {}", range));
+
+        let (network, prefix) = range.split_once('/').ok_or_else(invalid)?;
+        let network: IpAddr = network.parse().map_err(|_| invalid())?;
+        let prefix: u32 = prefix.parse().map_err(|_| invalid())?;
+
+        let bits = if network.is_ipv4() { 32 } else { 128 };
+        if prefix > bits {
+            return Err(invalid());
+        }
+
+        Ok(Self { network, prefix })
+    }
+
+    fn contains(&self, ip: IpAddr) -> bool {
+        // the mask is made in 128 bits, then shifted down to the low `bits` bits.
+        fn matches(network: u128, ip: u128, bits: u32, prefix: u32) -> bool {
+            let mask = !u128::MAX.checked_shr(prefix).unwrap_or(0) >> (128 - bits);
+            network & mask == ip & mask
+        }
+
+        match (self.network, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => matches(
+                u32::from(network).into(),
+                u32::from(ip).into(),
+                32,
+                self.prefix,
+            ),
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                matches(network.into(), ip.into(), 128, self.prefix)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Looks up host names, for [`ReverseDns`]. Bring your own, eg. backed by a dns client
+/// crate, or a stub in tests.
+pub trait Resolver: Send + Sync {
+    /// The host names of `ip`, by its ptr records.
+    fn reverse(&self, ip: IpAddr) -> Result<Vec<String>>;
+
+    /// The addresses `host` resolves to.
+    fn forward(&self, host: &str) -> Result<Vec<IpAddr>>;
+}
+
+/// Verifies bots the way their operators document, by a reverse lookup of the ip to a
+/// host name of the operator's domains, and a forward lookup of that name back to the
+/// same ip. A lookup that fails leaves the bot unknown rather than spoofed.
+pub struct ReverseDns<R> {
+    resolver: R,
+    // by bot name.
+    domains: HashMap<String, Vec<String>>,
+}
+
+impl<R: Resolver> ReverseDns<R> {
+    /// Knows of the domains Google and Bing document for Googlebot and BingBot.
+    pub fn new(resolver: R) -> Self {
+        Self {
+            resolver,
+            domains: HashMap::new(),
+        }
+        .with_domains(
+            "Googlebot",
+            ["googlebot.com", "google.com", "googleusercontent.com"],
+        )
+        .with_domains("BingBot", ["search.msn.com"])
+    }
+
+    /// The domains host names of the bot named `bot` end in, replacing any known
+    /// already.
+    pub fn with_domains<I, S>(mut self, bot: impl Into<String>, domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.domains
+            .insert(bot.into(), domains.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+impl<R: Resolver> BotVerifier for ReverseDns<R> {
+    fn verify(&self, bot: &Bot, _ua: &str, ip: IpAddr) -> Result<Verification> {
+        let Some(domains) = self.domains.get(&bot.name) else {
+            return Ok(Verification::Unknown);
+        };
+
+        let Ok(hosts) = self.resolver.reverse(ip) else {
+            return Ok(Verification::Unknown);
+        };
+
+        for host in hosts {
+            let host = host.trim_end_matches('.').to_lowercase();
+            let in_domain = domains.iter().any(|domain| {
+                host.strip_suffix(domain.as_str())
+                    .is_some_and(|sub| sub.ends_with('.'))
+            });
+            if !in_domain {
+                continue;
+            }
+
+            match self.resolver.forward(&host) {
+                Ok(ips) if ips.contains(&ip) => return Ok(Verification::Verified),
+                Ok(_) => {}
+                Err(_) => return Ok(Verification::Unknown),
+            }
+        }
+
+        Ok(Verification::Spoofed)
+    }
+}
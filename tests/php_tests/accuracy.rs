@@ -0,0 +1,159 @@
+use anyhow::Result;
+
+use rust_device_detector::accuracy::{Accuracy, VersionAccuracy};
+use rust_device_detector::device_detector::{DeviceDetector, VersionTruncation};
+
+const REDUCED_ANDROID: &str = "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36";
+const MAC_SAFARI: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Safari/605.1.15";
+const WINDOWS_CHROME: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+fn accuracy(
+    detector: &DeviceDetector,
+    ua: &str,
+    headers: Option<Vec<(String, String)>>,
+) -> Result<(bool, VersionAccuracy)> {
+    let detection = detector.parse(ua, headers)?;
+    let known = detection.get_known_device().expect("known device");
+    Ok((detection.reduced_ua(), known.version_accuracy))
+}
+
+#[test]
+fn test_reduced_user_agents() -> Result<()> {
+    use Accuracy::*;
+
+    let detector = DeviceDetector::new();
+
+    assert_eq!(
+        accuracy(&detector, REDUCED_ANDROID, None)?,
+        (
+            true,
+            VersionAccuracy {
+                client: Some(Approximate),
+                engine: Some(Approximate),
+                os: Some(Approximate),
+            }
+        )
+    );
+
+    // Windows 11 still says `NT 10.0`.
+    assert_eq!(
+        accuracy(&detector, WINDOWS_CHROME, None)?,
+        (
+            true,
+            VersionAccuracy {
+                client: Some(Approximate),
+                engine: Some(Approximate),
+                os: Some(Approximate),
+            }
+        )
+    );
+
+    // safari freezes macOS too, but not its own version.
+    assert_eq!(
+        accuracy(&detector, MAC_SAFARI, None)?,
+        (
+            true,
+            VersionAccuracy {
+                client: Some(Exact),
+                engine: Some(Exact),
+                os: Some(Approximate),
+            }
+        )
+    );
+
+    let ua = "Spotify/8.6.72 iOS/13.5.1 (iPhone9,2)";
+    assert_eq!(
+        accuracy(&detector, ua, None)?,
+        (
+            false,
+            VersionAccuracy {
+                client: Some(Exact),
+                engine: None,
+                os: Some(Exact),
+            }
+        )
+    );
+
+    // truncating versions doesn't hide that they were frozen.
+    let truncating = DeviceDetector::builder()
+        .version_truncation(VersionTruncation::Major)
+        .build();
+    assert_eq!(
+        accuracy(&truncating, REDUCED_ANDROID, None)?,
+        accuracy(&detector, REDUCED_ANDROID, None)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_versions_from_client_hints() -> Result<()> {
+    let detector = DeviceDetector::new();
+
+    let headers = vec![
+        ("sec-ch-ua-platform".to_owned(), r#""Android""#.to_owned()),
+        (
+            "sec-ch-ua-platform-version".to_owned(),
+            r#""13.0.0""#.to_owned(),
+        ),
+        (
+            "sec-ch-ua-full-version-list".to_owned(),
+            r#""Not_A Brand";v="8.0.0.0", "Chromium";v="120.0.6099.71", "Google Chrome";v="120.0.6099.71""#
+                .to_owned(),
+        ),
+    ];
+
+    // the hints carry the actual versions.
+    let (reduced, accuracy) = accuracy(&detector, REDUCED_ANDROID, Some(headers))?;
+    assert!(reduced);
+    assert_eq!(accuracy.client, Some(Accuracy::Exact));
+    assert_eq!(accuracy.os, Some(Accuracy::Exact));
+
+    let value = detector.parse(REDUCED_ANDROID, None)?.to_value();
+    assert_eq!(value["reduced_ua"], true);
+    assert_eq!(value["version_accuracy"]["os"], "approximate");
+
+    // both are left out for user agents giving exact versions.
+    let value = detector
+        .parse("Spotify/8.6.72 iOS/13.5.1 (iPhone9,2)", None)?
+        .to_value();
+    assert!(value.get("reduced_ua").is_none());
+    assert!(value.get("version_accuracy").is_none());
+
+    // bots are never reduced.
+    assert!(!detector
+        .parse("Googlebot/2.1 (+http://www.google.com/bot.html)", None)?
+        .reduced_ua());
+
+    Ok(())
+}
+
+#[test]
+fn test_android_k_with_model_hint() -> Result<()> {
+    let detector = DeviceDetector::new();
+
+    let ua = "Mozilla/5.0 (Linux; Android 14; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/136.0.7103.61 Mobile Safari/537.36";
+    let headers = vec![
+        ("sec-ch-ua-platform".to_owned(), r#""Android""#.to_owned()),
+        (
+            "sec-ch-ua-platform-version".to_owned(),
+            r#""14.0.0""#.to_owned(),
+        ),
+        ("sec-ch-ua-model".to_owned(), r#""SM-S918B""#.to_owned()),
+    ];
+
+    // later versions than 10 keep the major version, but still hide the model.
+    let value = detector.parse(ua, None)?.to_value();
+    assert_eq!(value["reduced_ua"], true);
+    assert_eq!(value["os"]["version"], "14");
+    assert_eq!(value["version_accuracy"]["os"], "approximate");
+    assert_eq!(value["device"]["model"], serde_json::Value::Null);
+
+    let value = detector.parse(ua, Some(headers))?.to_value();
+    assert_eq!(value["reduced_ua"], true);
+    assert_eq!(value["os"]["version"], "14.0.0");
+    assert_eq!(value["device"]["brand"], "Samsung");
+    assert_eq!(value["device"]["model"], "Galaxy S23 Ultra");
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anyhow::Result;
+
+use rust_device_detector::automation::Automation;
+use rust_device_detector::device_detector::DeviceDetector;
+
+fn automation(
+    detector: &DeviceDetector,
+    ua: &str,
+    headers: Option<Vec<(String, String)>>,
+) -> Result<Option<Automation>> {
+    let detection = detector.parse(ua, headers)?;
+    Ok(detection
+        .get_known_device()
+        .expect("known device")
+        .automation)
+}
+
+#[test]
+fn test_automation_tokens() -> Result<()> {
+    let detector = DeviceDetector::new();
+
+    let cases = [
+        (
+            "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) HeadlessChrome/120.0.6099.28 Safari/537.36",
+            Automation::HeadlessChrome,
+        ),
+        (
+            "Mozilla/5.0 (Unknown; Linux x86_64) AppleWebKit/538.1 (KHTML, like Gecko) PhantomJS/2.1.1 Safari/538.1",
+            Automation::PhantomJs,
+        ),
+        // the tool driving headless chrome is named over it.
+        (
+            "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) HeadlessChrome/120.0.6099.28 Safari/537.36 Puppeteer",
+            Automation::Puppeteer,
+        ),
+        (
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Playwright/1.40.0",
+            Automation::Playwright,
+        ),
+        (
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:121.0) Gecko/20100101 Firefox/121.0 Selenium",
+            Automation::Selenium,
+        ),
+    ];
+
+    for (ua, expected) in cases {
+        assert_eq!(automation(&detector, ua, None)?, Some(expected), "{}", ua);
+    }
+
+    // the browser is still detected as usual.
+    let detection = detector.parse(cases[0].0, None)?;
+    let known = detection.get_known_device().expect("known device");
+    assert!(known.is_automated());
+    assert_eq!(
+        known.client.as_ref().map(|client| client.name.as_str()),
+        Some("Headless Chrome")
+    );
+    assert!(!detection.is_bot());
+
+    let value = detection.to_value();
+    assert_eq!(value["automation"], "headless chrome");
+
+    Ok(())
+}
+
+#[test]
+fn test_automation_hints() -> Result<()> {
+    let detector = DeviceDetector::new();
+    let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+    assert_eq!(automation(&detector, ua, None)?, None);
+
+    // headless chrome sent with the user agent of an ordinary chrome.
+    let headers = vec![(
+        "sec-ch-ua".to_owned(),
+        r#""Not_A Brand";v="8", "Chromium";v="120", "HeadlessChrome";v="120""#.to_owned(),
+    )];
+    assert_eq!(
+        automation(&detector, ua, Some(headers))?,
+        Some(Automation::HeadlessChrome)
+    );
+
+    // left out for browsers used by people.
+    let value = detector.parse(ua, None)?.to_value();
+    assert!(value.get("automation").is_none());
+
+    Ok(())
+}
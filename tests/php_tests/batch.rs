@@ -0,0 +1,75 @@
+use anyhow::Result;
+
+use rust_device_detector::batch::Headers;
+use rust_device_detector::device_detector::DeviceDetector;
+
+const SPOTIFY: &str = "Spotify/8.6.72 iOS/13.5.1 (iPhone9,2)";
+const BOT: &str = "Googlebot/2.1 (+http://www.google.com/bot.html)";
+const CHROME: &str = "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Mobile Safari/537.36";
+
+fn inputs() -> Vec<(&'static str, Option<Headers>)> {
+    let hints = Some(vec![(
+        "sec-ch-ua-model".to_string(),
+        r#""Pixel 7""#.to_string(),
+    )]);
+
+    vec![
+        (SPOTIFY, None),
+        (BOT, None),
+        (CHROME, None),
+        (CHROME, hints.clone()),
+        (SPOTIFY, None),
+        (CHROME, hints),
+        ("", None),
+    ]
+}
+
+#[test]
+fn test_parse_batch() -> Result<()> {
+    let detector = DeviceDetector::new();
+
+    let inputs = inputs();
+    let results = detector.parse_batch(&inputs);
+    assert_eq!(results.len(), inputs.len());
+
+    // in input order, and the same as parsing one at a time, repeats included.
+    for ((ua, headers), res) in inputs.into_iter().zip(results) {
+        assert_eq!(
+            res?.to_value(),
+            detector.parse(ua, headers.clone())?.to_value(),
+            "ua: {} headers: {:?}",
+            ua,
+            headers
+        );
+    }
+
+    assert!(detector.parse_batch::<&str>(&[]).is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_iter() -> Result<()> {
+    let detector = DeviceDetector::new();
+
+    let expected: Vec<_> = inputs()
+        .into_iter()
+        .map(|(ua, headers)| detector.parse(ua, headers).map(|res| res.to_value()))
+        .collect::<Result<_, _>>()?;
+
+    for batch_size in [1, 2, 3, 100] {
+        let results: Vec<_> = detector
+            .parse_iter(
+                inputs()
+                    .into_iter()
+                    .map(|(ua, headers)| (ua.to_owned(), headers)),
+            )
+            .batch_size(batch_size)
+            .map(|res| res.map(|res| res.to_value()))
+            .collect::<Result<_, _>>()?;
+
+        assert_eq!(results, expected, "batch size: {}", batch_size);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anyhow::Result;
+
+use rust_device_detector::device_detector::DeviceDetector;
+use rust_device_detector::parsers::bot::BotCategory;
+use rust_device_detector::rule_database::RuleDatabase;
+
+const PARTNER_BOTS: &str = r#"
+- regex: 'AcmePartnerBot'
+  name: 'Acme Partner Bot'
+  category: 'Partner'
+  producer:
+    name: 'Acme'
+    url: 'https://acme.example'
+"#;
+
+#[test]
+fn test_bot_categories() -> Result<()> {
+    let database = RuleDatabase::embedded();
+    let bots = database.bots().all();
+    assert!(!bots.is_empty());
+
+    // every category upstream uses has a variant of its own.
+    for bot in &bots {
+        assert!(
+            !matches!(bot.category, Some(BotCategory::Other(_))),
+            "{}: {:?}",
+            bot.name,
+            bot.category
+        );
+    }
+
+    let detection =
+        DeviceDetector::new().parse("Googlebot/2.1 (+http://www.google.com/bot.html)", None)?;
+    let bot = detection.get_bot().expect("bot");
+    assert_eq!(bot.category, Some(BotCategory::SearchBot));
+    assert_eq!(detection.to_value()["bot"]["category"], "Search bot");
+
+    Ok(())
+}
+
+#[test]
+fn test_bot_catalog() -> Result<()> {
+    let database = RuleDatabase::embedded();
+    let bots = database.bots();
+
+    assert!(bots.contains("Googlebot"));
+    assert!(!bots.contains("googlebot"));
+    assert!(!bots.contains("Acme Partner Bot"));
+
+    // names are listed once, however many rules detect them.
+    let all = bots.all();
+    let googlebots = all.iter().filter(|bot| bot.name == "Googlebot").count();
+    assert_eq!(googlebots, 1);
+
+    let search_bots = bots.by_category(&BotCategory::SearchBot);
+    assert!(search_bots.iter().any(|bot| bot.name == "Googlebot"));
+    assert!(search_bots
+        .iter()
+        .all(|bot| bot.category == Some(BotCategory::SearchBot)));
+
+    let google = bots.by_producer("google inc.");
+    assert!(google.iter().any(|bot| bot.name == "Googlebot"));
+    assert!(google.iter().all(|bot| bot
+        .producer
+        .as_ref()
+        .and_then(|producer| producer.name.as_deref())
+        == Some("Google Inc.")));
+
+    Ok(())
+}
+
+#[test]
+fn test_other_bot_category() -> Result<()> {
+    let database = RuleDatabase::builder()
+        .overlay("bots.yml", "partner bots", PARTNER_BOTS)
+        .build()?;
+    let bots = database.bots();
+
+    let partner = BotCategory::Other("Partner".to_owned());
+    let partners = bots.by_category(&partner);
+    assert_eq!(partners.len(), 1);
+    assert_eq!(partners[0].name, "Acme Partner Bot");
+    assert_eq!(partner.as_str(), "Partner");
+    assert_eq!(BotCategory::from_str("Partner"), partner);
+
+    assert!(bots.contains("Acme Partner Bot"));
+    assert_eq!(bots.by_producer("Acme").len(), 1);
+
+    Ok(())
+}
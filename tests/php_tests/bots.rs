@@ -28,7 +28,10 @@ pub(crate) fn basic(idx: usize, value: &Value) -> Result<()> {
         ua
     );
 
-    let dd_bot_category: Option<&str> = dd_res.get_bot().and_then(|bot| bot.category.as_deref());
+    let dd_bot_category: Option<&str> = dd_res
+        .get_bot()
+        .and_then(|bot| bot.category.as_ref())
+        .map(|category| category.as_str());
     let test_bot_category: Option<&str> = test_bot.get("category").and_then(|cat| cat.as_str());
 
     assert!(
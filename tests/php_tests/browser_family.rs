@@ -0,0 +1,40 @@
+use anyhow::Result;
+
+use rust_device_detector::device_detector::DeviceDetector;
+
+#[test]
+fn test_browser_family() -> Result<()> {
+    let detector = DeviceDetector::new();
+
+    let ua = "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Mobile Safari/537.36";
+    let detection = detector.parse(ua, None)?;
+    let client = detection
+        .get_known_device()
+        .and_then(|known| known.client.as_ref())
+        .expect("client");
+    assert_eq!(client.family(), Some("Chrome"));
+    assert!(!client.mobile_only());
+
+    let value = detection.to_value();
+    assert_eq!(value["client"]["family"], "Chrome");
+    assert_eq!(value["client"]["mobile_only"], false);
+
+    let ua = "Opera/9.80 (Android 4.1.2; Linux; Opera Mobi/ADR-1212030829) Presto/2.11.355 Version/12.10";
+    let detection = detector.parse(ua, None)?;
+    let client = detection
+        .get_known_device()
+        .and_then(|known| known.client.as_ref())
+        .expect("client");
+    assert_eq!(client.name, "Opera Mobile");
+    assert_eq!(client.family(), Some("Opera"));
+    assert!(client.mobile_only());
+    assert_eq!(detection.to_value()["client"]["mobile_only"], true);
+
+    // only browsers have a family.
+    let detection = detector.parse("Spotify/8.6.72 iOS/13.5.1 (iPhone9,2)", None)?;
+    let value = detection.to_value();
+    assert!(value["client"]["family"].is_null());
+    assert_eq!(value["client"]["mobile_only"], false);
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use anyhow::Result;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use rust_device_detector::cache::{CacheStats, DetectionCache, NoCache};
+use rust_device_detector::device_detector::{Detection, DeviceDetector};
+use rust_device_detector::rule_database::RuleDatabase;
+
+const UA: &str = "Spotify/8.6.72 iOS/13.5.1 (iPhone9,2)";
+const OTHER_UA: &str = "Googlebot/2.1 (+http://www.google.com/bot.html)";
+
+// what a service's own cache layer might look like.
+#[derive(Default)]
+struct SharedCache {
+    entries: Mutex<HashMap<String, Detection>>,
+}
+
+impl DetectionCache for SharedCache {
+    fn get(&self, key: &str) -> Option<Detection> {
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+
+    fn insert(&self, key: String, detection: Detection) {
+        self.entries.lock().unwrap().insert(key, detection);
+    }
+
+    fn invalidate_all(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+#[test]
+fn test_custom_cache() -> Result<()> {
+    let cache = Arc::new(SharedCache::default());
+    let detector = DeviceDetector::builder().cache(cache.clone()).build();
+
+    let detection = detector.parse(UA, None)?.to_value();
+    assert_eq!(cache.entries.lock().unwrap().len(), 1);
+
+    // served from the cache from now on.
+    let cached = cache
+        .entries
+        .lock()
+        .unwrap()
+        .values()
+        .next()
+        .cloned()
+        .unwrap();
+    assert_eq!(cached.to_value(), detection);
+    assert_eq!(detector.parse(UA, None)?.to_value(), detection);
+
+    detector.parse(OTHER_UA, None)?;
+    assert_eq!(cache.entries.lock().unwrap().len(), 2);
+
+    // a detector rebuilt with the same database is served its entries.
+    let rebuilt = detector.rebuild_with_database(detector.database().clone());
+    rebuilt.parse(UA, None)?;
+    assert_eq!(cache.entries.lock().unwrap().len(), 2);
+
+    // one rebuilt with another database shares the cache, but neither detector is
+    // served the other's detections.
+    let bots = r#"
+- regex: 'Spotify'
+  name: 'Spotify Bot'
+"#;
+    let database = RuleDatabase::from_sources([("bots.yml", bots)])?;
+    let rebuilt = detector.rebuild_with_database(Arc::new(database));
+    assert!(rebuilt.parse(UA, None)?.is_bot());
+    assert_eq!(cache.entries.lock().unwrap().len(), 3);
+    assert_eq!(detector.parse(UA, None)?.to_value(), detection);
+
+    cache.invalidate_all();
+    assert_eq!(cache.entries.lock().unwrap().len(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_no_cache() -> Result<()> {
+    let detector = DeviceDetector::builder().cache(NoCache::new()).build();
+
+    detector.parse(UA, None)?;
+    detector.parse(UA, None)?;
+
+    assert_eq!(
+        detector.cache_stats(),
+        Some(CacheStats {
+            hits: 0,
+            misses: 2,
+            evictions: 0
+        })
+    );
+
+    // without any cache there is nothing to count.
+    assert_eq!(DeviceDetector::new().cache_stats(), None);
+
+    Ok(())
+}
+
+#[cfg(feature = "cache")]
+#[test]
+fn test_thread_local_lru_cache() -> Result<()> {
+    use rust_device_detector::cache::ThreadLocalLruCache;
+
+    let cache = Arc::new(ThreadLocalLruCache::new(1));
+    let detector = DeviceDetector::builder().cache(cache.clone()).build();
+
+    detector.parse(UA, None)?;
+    detector.parse(UA, None)?;
+    // evicts UA, leaving room for one entry per thread only.
+    detector.parse(OTHER_UA, None)?;
+    detector.parse(UA, None)?;
+
+    assert_eq!(
+        detector.cache_stats(),
+        Some(CacheStats {
+            hits: 1,
+            misses: 3,
+            evictions: 2
+        })
+    );
+
+    // another thread has a cache of its own.
+    std::thread::scope(|scope| {
+        scope.spawn(|| detector.parse(UA, None).unwrap());
+    });
+    assert_eq!(detector.cache_stats().map(|stats| stats.misses), Some(4));
+
+    // a rebuilt detector shares the cache, and its counts.
+    let rebuilt = detector.rebuild_with_database(detector.database().clone());
+    rebuilt.parse(UA, None)?;
+    assert_eq!(rebuilt.cache_stats().map(|stats| stats.hits), Some(2));
+    assert_eq!(detector.cache_stats(), rebuilt.cache_stats());
+
+    // invalidating empties every thread's cache.
+    cache.invalidate_all();
+    detector.parse(UA, None)?;
+    assert_eq!(detector.cache_stats().map(|stats| stats.misses), Some(5));
+
+    Ok(())
+}
+
+#[cfg(feature = "cache")]
+#[test]
+fn test_moka_cache() -> Result<()> {
+    use rust_device_detector::cache::MokaCache;
+
+    let detector = DeviceDetector::builder()
+        .cache(MokaCache::with_max_bytes(1024 * 1024))
+        .build();
+
+    let detection = detector.parse(UA, None)?.to_value();
+    assert_eq!(detector.parse(UA, None)?.to_value(), detection);
+
+    assert_eq!(
+        detector.cache_stats(),
+        Some(CacheStats {
+            hits: 1,
+            misses: 1,
+            evictions: 0
+        })
+    );
+
+    Ok(())
+}
@@ -45,4 +45,42 @@ fn test_form_factors_device_detection() -> Result<()> {
     assert_eq!(device_type, Some("desktop"));
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(feature = "cache")]
+#[test]
+fn test_cache_keyed_by_client_hints() -> Result<()> {
+    let cached = DeviceDetector::new().with_cache(100);
+    let uncached = DeviceDetector::new();
+
+    // a reduced user agent, only the client hints tell the actual device apart.
+    let ua = "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Mobile Safari/537.36";
+    let hints = |model: &str| {
+        Some(vec![
+            ("sec-ch-ua-model".to_string(), format!(r#""{}""#, model)),
+            ("sec-ch-ua-platform".to_string(), r#""Android""#.to_string()),
+            ("sec-ch-ua-platform-version".to_string(), r#""13.0.0""#.to_string()),
+        ])
+    };
+
+    for headers in [None, hints("Pixel 7"), hints("SM-G991B"), hints("Pixel 7"), None] {
+        assert_eq!(
+            cached.parse(ua, headers.clone())?.to_value(),
+            uncached.parse(ua, headers.clone())?.to_value(),
+            "headers: {:?}",
+            headers
+        );
+    }
+
+    let model = |headers| -> Result<Option<String>> {
+        Ok(cached
+            .parse(ua, headers)?
+            .get_known_device()
+            .and_then(|dev| dev.device.as_ref())
+            .and_then(|dev| dev.model.clone()))
+    };
+    assert_eq!(model(hints("Pixel 7"))?.as_deref(), Some("Pixel 7"));
+    assert_eq!(model(hints("SM-G991B"))?.as_deref(), Some("Galaxy S21 5G"));
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anyhow::Result;
+
+use std::sync::Arc;
+
+use rust_device_detector::device_detector::DeviceDetector;
+use rust_device_detector::parsers::client::Client;
+use rust_device_detector::rule_database::RuleDatabase;
+
+const FEED_READERS: &str = r#"
+- regex: 'AcmeReader(?:/(\d+[.\d]+))?'
+  name: 'Acme Reader'
+  version: '$1'
+  url: 'https://acme.example/reader'
+  type: 'Feed Reader App'
+"#;
+
+fn client(detector: &DeviceDetector, ua: &str) -> Result<Client> {
+    Ok(detector
+        .parse(ua, None)?
+        .get_known_device()
+        .and_then(|known| known.client.clone())
+        .expect("client"))
+}
+
+#[test]
+fn test_client_urls() -> Result<()> {
+    let detector = DeviceDetector::new();
+
+    let akregator = client(&detector, "Akregator/4.11.5; librss/remnants")?;
+    assert_eq!(
+        akregator.url.as_deref(),
+        Some("http://userbase.kde.org/Akregator")
+    );
+    assert_eq!(akregator.subtype, None);
+
+    // libraries carry urls too.
+    let phantom = client(&detector, "Mozilla/5.0 (Unknown; Linux x86_64) AppleWebKit/538.1 (KHTML, like Gecko) PhantomJS/2.1.1 Safari/538.1")?;
+    assert_eq!(phantom.name, "PhantomJS");
+    assert_eq!(phantom.url.as_deref(), Some("https://phantomjs.org/"));
+
+    let spotify = client(&detector, "Spotify/8.6.72 iOS/13.5.1 (iPhone9,2)")?;
+    assert_eq!(spotify.url, None);
+
+    let value = detector
+        .parse("Akregator/4.11.5; librss/remnants", None)?
+        .to_value();
+    assert_eq!(value["client"]["url"], "http://userbase.kde.org/Akregator");
+    assert!(value["client"].get("subtype").is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_client_subtype() -> Result<()> {
+    let database = RuleDatabase::builder()
+        .overlay("client/feed_readers.yml", "readers", FEED_READERS)
+        .build()?;
+    let detector = DeviceDetector::with_database(Arc::new(database));
+
+    let reader = client(&detector, "AcmeReader/2.1")?;
+    assert_eq!(reader.name, "Acme Reader");
+    assert_eq!(reader.url.as_deref(), Some("https://acme.example/reader"));
+    assert_eq!(reader.subtype.as_deref(), Some("Feed Reader App"));
+
+    Ok(())
+}
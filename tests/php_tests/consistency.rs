@@ -0,0 +1,200 @@
+use anyhow::Result;
+
+use rust_device_detector::consistency::Inconsistency;
+use rust_device_detector::device_detector::DeviceDetector;
+use rust_device_detector::parsers::device::DeviceType;
+use rust_device_detector::trace::Part;
+
+const WINDOWS_CHROME: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+const MAC_CHROME: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+const ANDROID_CHROME: &str = "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36";
+
+fn headers(pairs: &[(&str, &str)]) -> Option<Vec<(String, String)>> {
+    Some(
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+    )
+}
+
+fn chrome_hints(platform: &str, version: &str, mobile: bool) -> Vec<(&'static str, String)> {
+    vec![
+        (
+            "sec-ch-ua",
+            r#""Not_A Brand";v="8", "Chromium";v="120", "Google Chrome";v="120""#.to_owned(),
+        ),
+        (
+            "sec-ch-ua-full-version-list",
+            r#""Not_A Brand";v="8.0.0.0", "Chromium";v="120.0.6099.71", "Google Chrome";v="120.0.6099.71""#
+                .to_owned(),
+        ),
+        ("sec-ch-ua-mobile", if mobile { "?1" } else { "?0" }.to_owned()),
+        ("sec-ch-ua-platform", format!(r#""{}""#, platform)),
+        ("sec-ch-ua-platform-version", format!(r#""{}""#, version)),
+    ]
+}
+
+fn check(ua: &str, hints: &[(&str, String)]) -> Result<Vec<Inconsistency>> {
+    let detector = DeviceDetector::builder().check_consistency(true).build();
+    let pairs: Vec<(&str, &str)> = hints
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect();
+    let detection = detector.parse(ua, headers(&pairs))?;
+    Ok(detection.consistency().expect("checked").to_vec())
+}
+
+#[test]
+fn test_consistent() -> Result<()> {
+    let mut hints = chrome_hints("Android", "13.0.0", true);
+    hints.push(("sec-ch-ua-model", r#""Pixel 7""#.to_owned()));
+
+    // a reduced user agent, its frozen os version isn't held against it.
+    assert_eq!(check(ANDROID_CHROME, &hints)?, vec![]);
+    // nor is Windows 11 saying `NT 10.0`, or macOS saying 10.15.7.
+    assert_eq!(
+        check(WINDOWS_CHROME, &chrome_hints("Windows", "15.0.0", false))?,
+        vec![]
+    );
+    assert_eq!(
+        check(MAC_CHROME, &chrome_hints("macOS", "14.2.0", false))?,
+        vec![]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_platform() -> Result<()> {
+    assert_eq!(
+        check(WINDOWS_CHROME, &chrome_hints("Android", "13.0.0", true))?,
+        vec![Inconsistency::Platform {
+            hint: "Android".to_owned(),
+            ua: "Windows".to_owned(),
+        }]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_brand() -> Result<()> {
+    let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:121.0) Gecko/20100101 Firefox/121.0";
+    assert_eq!(
+        check(ua, &chrome_hints("Windows", "15.0.0", false))?,
+        vec![Inconsistency::Brand {
+            hints: vec!["Chromium".to_owned(), "Google Chrome".to_owned()],
+            ua: "Firefox".to_owned(),
+        }]
+    );
+
+    // any browser of the Chrome family goes with a Chromium brand, Vivaldi doesn't
+    // send one of its own.
+    let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Vivaldi/6.5.3206.39";
+    assert_eq!(
+        check(ua, &chrome_hints("Windows", "15.0.0", false))?,
+        vec![]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_mobile() -> Result<()> {
+    let ua = "Mozilla/5.0 (Linux; Android 13; SM-G991B) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36";
+    assert_eq!(
+        check(ua, &chrome_hints("Android", "13.0.0", false))?,
+        vec![Inconsistency::Mobile {
+            hint: false,
+            device_type: DeviceType::SmartPhone,
+        }]
+    );
+
+    assert_eq!(
+        check(WINDOWS_CHROME, &chrome_hints("Windows", "15.0.0", true))?,
+        vec![Inconsistency::Mobile {
+            hint: true,
+            device_type: DeviceType::Desktop,
+        }]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_model() -> Result<()> {
+    let ua = "Mozilla/5.0 (Linux; Android 13; SM-G991B) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36";
+    let mut hints = chrome_hints("Android", "13.0.0", true);
+    hints.push(("sec-ch-ua-model", r#""Pixel 7""#.to_owned()));
+
+    assert_eq!(
+        check(ua, &hints)?,
+        vec![Inconsistency::Model {
+            hint: "Pixel 7".to_owned(),
+            ua: "Samsung Galaxy S21 5G".to_owned(),
+        }]
+    );
+
+    hints.pop();
+    hints.push(("sec-ch-ua-model", r#""SM-G991B""#.to_owned()));
+    assert_eq!(check(ua, &hints)?, vec![]);
+
+    Ok(())
+}
+
+#[test]
+fn test_versions() -> Result<()> {
+    let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/99.0.4844.51 Safari/537.36";
+    assert_eq!(
+        check(ua, &chrome_hints("Windows", "15.0.0", false))?,
+        vec![Inconsistency::Version {
+            part: Part::Client,
+            hint: "120.0.6099.71".to_owned(),
+            ua: "99".to_owned(),
+        }]
+    );
+
+    // Windows 7 can't send Windows 11 hints.
+    let ua = "Mozilla/5.0 (Windows NT 6.1; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+    assert_eq!(
+        check(ua, &chrome_hints("Windows", "15.0.0", false))?,
+        vec![Inconsistency::Version {
+            part: Part::Os,
+            hint: "11".to_owned(),
+            ua: "7".to_owned(),
+        }]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_unchecked() -> Result<()> {
+    let hints = chrome_hints("Android", "13.0.0", true);
+    let pairs: Vec<(&str, &str)> = hints
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect();
+
+    // only checked when asked to.
+    let detection = DeviceDetector::new().parse(WINDOWS_CHROME, headers(&pairs))?;
+    assert!(detection.consistency().is_none());
+    assert!(detection.to_value().get("consistency").is_none());
+
+    // nothing to disagree with without hints.
+    let detector = DeviceDetector::builder().check_consistency(true).build();
+    let detection = detector.parse(WINDOWS_CHROME, None)?;
+    assert_eq!(detection.consistency(), Some(&[][..]));
+
+    let detection = detector.parse(WINDOWS_CHROME, headers(&pairs))?;
+    assert_eq!(detection.to_value()["consistency"][0]["kind"], "platform");
+
+    // the user agent is looked up again on its own, which isn't part of the trace.
+    let checked = detector.explain(WINDOWS_CHROME, headers(&pairs))?.trace;
+    let unchecked = DeviceDetector::new()
+        .explain(WINDOWS_CHROME, headers(&pairs))?
+        .trace;
+    assert_eq!(checked, unchecked);
+
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use anyhow::Result;
+
+use rust_device_detector::client_hints::ClientHint;
+use rust_device_detector::device_detector::DeviceDetector;
+use rust_device_detector::error::DetectError;
+use rust_device_detector::rule_database::RuleDatabase;
+
+fn headers(headers: &[(&str, &str)]) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_error_codes() {
+    // these are reported over ffi and http, and must never change.
+    let errors = [
+        (
+            DetectError::InvalidInput("not utf8".to_owned()),
+            1,
+            "invalid_input",
+        ),
+        (
+            DetectError::InvalidClientHint {
+                header: "sec-ch-ua-mobile".to_owned(),
+                value: "maybe".to_owned(),
+            },
+            2,
+            "invalid_client_hint",
+        ),
+        (
+            DetectError::RuleDatabase {
+                file: "bots.yml".to_owned(),
+                rule: Some(0),
+                pattern: None,
+                message: "broken".to_owned(),
+            },
+            3,
+            "rule_database",
+        ),
+        (
+            DetectError::RegexLimit {
+                pattern: "(a+)+b".to_owned(),
+                message: "backtrack limit exceeded".to_owned(),
+            },
+            4,
+            "regex_limit",
+        ),
+    ];
+
+    for (err, code, name) in errors {
+        assert_eq!(err.code(), code, "{}", err);
+        assert_eq!(err.name(), name, "{}", err);
+    }
+}
+
+#[test]
+fn test_strict_client_hints() -> Result<()> {
+    let malformed = headers(&[("Sec-CH-UA", "Google Chrome 81")]);
+
+    // ignored like upstream does, unless asked to be strict.
+    assert!(ClientHint::from_headers(malformed.clone())?
+        .full_version_list
+        .is_empty());
+
+    let err = ClientHint::from_headers_strict(malformed.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        DetectError::InvalidClientHint {
+            header: "sec-ch-ua".to_owned(),
+            value: "Google Chrome 81".to_owned(),
+        }
+    );
+
+    let ua = "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Mobile Safari/537.36";
+    assert!(DeviceDetector::new()
+        .parse(ua, Some(malformed.clone()))
+        .is_ok());
+
+    let detector = DeviceDetector::builder().strict_client_hints(true).build();
+    assert_eq!(
+        detector
+            .parse(ua, Some(malformed))
+            .map(|_| ())
+            .unwrap_err()
+            .code(),
+        2
+    );
+
+    for (header, value) in [
+        ("sec-ch-ua-mobile", "yes"),
+        (
+            "sec-ch-ua-full-version-list",
+            r#""Chromium";v="114", Chrome"#,
+        ),
+        ("sec-ch-ua-form-factors", "Desktop"),
+    ] {
+        assert!(
+            ClientHint::from_headers_strict(headers(&[(header, value)])).is_err(),
+            "{}: {}",
+            header,
+            value
+        );
+    }
+
+    let valid = headers(&[
+        (
+            "Sec-CH-UA",
+            r#""Not.A/Brand";v="8", "Chromium";v="114", "Google Chrome";v="114""#,
+        ),
+        ("Sec-CH-UA-Mobile", "?1"),
+        ("Sec-CH-UA-Form-Factors", r#""Mobile", "Tablet""#),
+        ("Sec-CH-UA-Model", r#""Pixel 7""#),
+    ]);
+    assert!(detector.parse(ua, Some(valid)).is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_rule_database_errors() -> Result<()> {
+    let err = RuleDatabase::from_sources([("bots.yml", "- regex: [")]).unwrap_err();
+    assert!(matches!(
+        &err,
+        DetectError::RuleDatabase { file, rule: None, .. } if file == "bots.yml"
+    ));
+
+    let err = RuleDatabase::from_sources([("bots.yaml", "")]).unwrap_err();
+    assert_eq!(err.code(), 3);
+
+    let err = RuleDatabase::from_dir("/nonexistent/regexes").unwrap_err();
+    assert_eq!(err.code(), 3);
+
+    let mobiles = r#"
+Acme:
+  regex: 'Acme'
+  device: 'smartphone'
+  models:
+    - regex: 'Acme ([^;/]+)'
+      model: ['not', 'a', 'model']
+"#;
+    let err = RuleDatabase::from_sources([("device/mobiles.yml", mobiles)]).unwrap_err();
+    assert!(
+        matches!(&err, DetectError::RuleDatabase { file, message, .. }
+            if file == "device/mobiles.yml" && message.contains("invalid model")),
+        "{:?}",
+        err
+    );
+
+    // a broken regex is found on validation, or on first use, either way with its
+    // file and rule.
+    let bots = r#"
+- regex: 'FineBot'
+  name: 'Fine Bot'
+- regex: 'Broken(Bot'
+  name: 'Broken Bot'
+"#;
+    let expected = |err: DetectError| {
+        assert!(
+            matches!(
+                &err,
+                DetectError::RuleDatabase { file, rule: Some(1), pattern: Some(_), .. }
+                    if file == "bots.yml"
+            ),
+            "{:?}",
+            err
+        );
+    };
+
+    expected(
+        RuleDatabase::from_sources([("bots.yml", bots)])?
+            .validate()
+            .unwrap_err(),
+    );
+
+    let detector = DeviceDetector::from_sources([("bots.yml", bots)])?;
+    expected(detector.parse("SomeBot/1.0", None).map(|_| ()).unwrap_err());
+
+    Ok(())
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn test_ffi_last_error() {
+    use rust_device_detector::ffi::*;
+    use std::ffi::{CStr, CString};
+
+    let dir = CString::new("/nonexistent/regexes").unwrap();
+    let rdd = unsafe { rdd_device_detector_from_dir(dir.as_ptr(), 0) };
+    assert!(rdd.is_null());
+    assert_eq!(rdd_last_error_code(), 3);
+
+    let message = rdd_last_error_message();
+    assert!(unsafe { CStr::from_ptr(message) }
+        .to_str()
+        .unwrap()
+        .contains("/nonexistent/regexes"));
+    unsafe { rdd_free_string(message) };
+}
+
+#[test]
+fn test_regex_limit() -> Result<()> {
+    // the look ahead needs the backtracking engine, which tries every way of
+    // splitting the a's before giving up.
+    let bots = r#"
+- regex: '^(?:(?=a)a|a)+b'
+  name: 'Pathological Bot'
+"#;
+
+    let detector = DeviceDetector::from_sources([("bots.yml", bots)])?;
+    assert!(detector.parse("aab", None)?.is_bot());
+
+    let err = detector
+        .parse(&"a".repeat(64), None)
+        .map(|_| ())
+        .unwrap_err();
+    assert_eq!(err.code(), 4, "{}", err);
+    assert!(
+        matches!(&err, DetectError::RegexLimit { pattern, .. } if pattern.contains("(?=a)")),
+        "{:?}",
+        err
+    );
+
+    Ok(())
+}
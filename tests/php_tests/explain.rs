@@ -0,0 +1,117 @@
+use anyhow::Result;
+
+use rust_device_detector::cache::NoCache;
+use rust_device_detector::device_detector::DeviceDetector;
+use rust_device_detector::trace::{Part, Reason, TraceStep};
+
+const SPOTIFY: &str = "Spotify/8.6.72 iOS/13.5.1 (iPhone9,2)";
+
+fn rule(part: Part, file: &str, rule: usize, model: Option<usize>) -> TraceStep {
+    TraceStep::Rule {
+        part,
+        file: file.to_owned(),
+        overlay: None,
+        rule,
+        model,
+    }
+}
+
+fn rules(trace: &[TraceStep], part: Part) -> Vec<(String, Option<usize>)> {
+    trace
+        .iter()
+        .filter_map(|step| match step {
+            TraceStep::Rule {
+                part: p,
+                file,
+                model,
+                ..
+            } if *p == part => Some((file.clone(), *model)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_explain_rules() -> Result<()> {
+    let detector = DeviceDetector::new();
+
+    let explanation = detector.explain(SPOTIFY, None)?;
+    assert_eq!(
+        explanation.detection.to_value(),
+        detector.parse(SPOTIFY, None)?.to_value()
+    );
+
+    let trace = &explanation.trace;
+    assert_eq!(rules(trace, Part::Os), [("oss.yml".to_owned(), None)]);
+    assert_eq!(
+        rules(trace, Part::Client),
+        [("client/mobile_apps.yml".to_owned(), None)]
+    );
+
+    // the iPhone9,2 entry within Apple's models.
+    let device = rules(trace, Part::Device);
+    assert_eq!(device.len(), 1);
+    assert_eq!(device[0].0, "device/mobiles.yml");
+    assert!(device[0].1.is_some());
+
+    let explanation = detector.explain("Googlebot/2.1 (+http://www.google.com/bot.html)", None)?;
+    assert!(explanation.detection.is_bot());
+    assert!(matches!(
+        explanation.trace.as_slice(),
+        [TraceStep::Rule { part: Part::Bot, file, .. }] if file == "bots.yml"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_explain_overrides() -> Result<()> {
+    let detector = DeviceDetector::new();
+
+    // no "Mobile", so android's chrome is a tablet.
+    let ua = "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Safari/537.36";
+    let trace = detector.explain(ua, None)?.trace;
+    assert!(trace.contains(&TraceStep::Override {
+        part: Part::Device,
+        field: "type",
+        value: Some("tablet".to_owned()),
+        reason: Reason::AndroidHeuristics,
+    }));
+
+    let ua = "Mozilla/5.0 (Windows NT 6.1; WOW64; Trident/7.0; MDDRJS; rv:11.0) like Gecko";
+    let trace = detector.explain(ua, None)?.trace;
+    assert!(trace.contains(&rule(Part::Device, "vendorfragments.yml", 0, None)));
+    assert!(trace.contains(&TraceStep::Override {
+        part: Part::Device,
+        field: "brand",
+        value: Some("Dell".to_owned()),
+        reason: Reason::VendorFragment,
+    }));
+
+    // a model no rule knows, so it can only come from the hints.
+    let headers = vec![(
+        "Sec-CH-UA-Model".to_owned(),
+        r#""Frobnicator 9000""#.to_owned(),
+    )];
+    let ua = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Safari/537.36";
+    let trace = detector.explain(ua, Some(headers))?.trace;
+    assert!(trace.contains(&TraceStep::Override {
+        part: Part::Device,
+        field: "model",
+        value: Some("Frobnicator 9000".to_owned()),
+        reason: Reason::ClientHints,
+    }));
+
+    Ok(())
+}
+
+#[test]
+fn test_explain_bypasses_cache() -> Result<()> {
+    let detector = DeviceDetector::builder().cache(NoCache::new()).build();
+
+    detector.parse(SPOTIFY, None)?;
+    assert!(!detector.explain(SPOTIFY, None)?.trace.is_empty());
+    assert_eq!(detector.cache_stats().map(|stats| stats.misses), Some(1));
+
+    Ok(())
+}
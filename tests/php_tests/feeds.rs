@@ -0,0 +1,90 @@
+use anyhow::Result;
+
+use std::sync::Arc;
+
+use rust_device_detector::device_detector::DeviceDetector;
+use rust_device_detector::rule_database::RuleDatabase;
+
+const NEWSBLUR: &str = "NewsBlur Feed Fetcher - 7 subscribers - http://www.newsblur.com/site/1948420/analytics-piwik (Mozilla/5.0 (Macintosh; Intel Mac OS X 10_7_1) AppleWebKit/534.48.3 (KHTML, like Gecko) Version/5.1 Safari/534.48.3)";
+
+// newsblur puts its feed id in a url rather than a feed-id field.
+const BOTS: &str = r#"
+- regex: 'NewsBlur .*(?:Fetcher|Finder)'
+  name: 'NewsBlur'
+  category: 'Feed Fetcher'
+  feed_id: 'newsblur\.com/site/(\d+)'
+"#;
+
+#[test]
+fn test_bot_subscribers() -> Result<()> {
+    let detector = DeviceDetector::new();
+
+    for (ua, subscribers, feed_id) in [
+        ("Feedbin - 9 subscribers", Some(9), None),
+        (
+            "Netvibes (http://www.netvibes.com/; 8 subscribers; feedID: 2244192)",
+            Some(8),
+            Some("2244192"),
+        ),
+        (
+            "Mozilla/5.0 (compatible; theoldreader.com; 1 subscribers; feed-id=aaa)",
+            Some(1),
+            Some("aaa"),
+        ),
+        (NEWSBLUR, Some(7), None),
+        ("Googlebot/2.1 (+http://www.google.com/bot.html)", None, None),
+        // matched by the rule of Googlebot, a search bot, by the rule's own regexes.
+        (
+            "Feedfetcher-Google; (+http://www.google.com/feedfetcher.html; 19 subscribers; feed-id=13965549748850348809)",
+            Some(19),
+            Some("13965549748850348809"),
+        ),
+    ] {
+        let detection = detector.parse(ua, None)?;
+        let bot = detection.get_bot().expect("bot");
+        assert_eq!(bot.subscribers, subscribers, "{}", ua);
+        assert_eq!(bot.feed_id.as_deref(), feed_id, "{}", ua);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_feed_reader_subscribers() -> Result<()> {
+    let detector = DeviceDetector::new();
+
+    let detection = detector.parse(
+        "Breaker/v315 (subscribers=9999; feed-id=123456; url=https://www.breaker.audio/url-slug-to-podcast)",
+        None,
+    )?;
+    let client = detection
+        .get_known_device()
+        .and_then(|known| known.client.as_ref())
+        .expect("client");
+    assert_eq!(client.name, "Breaker");
+    assert_eq!(client.subscribers, Some(9999));
+    assert_eq!(client.feed_id.as_deref(), Some("123456"));
+
+    let value = detection.to_value();
+    assert_eq!(value["client"]["subscribers"], 9999);
+    assert_eq!(value["client"]["feed_id"], "123456");
+
+    Ok(())
+}
+
+#[test]
+fn test_rule_feed_ids() -> Result<()> {
+    let database = RuleDatabase::builder()
+        .overlay("bots.yml", "newsblur", BOTS)
+        .build()?;
+    database.validate()?;
+    let detector = DeviceDetector::with_database(Arc::new(database));
+
+    let detection = detector.parse(NEWSBLUR, None)?;
+    let bot = detection.get_bot().expect("bot");
+    assert_eq!(bot.feed_id.as_deref(), Some("1948420"));
+    // the default is still used for anything the rule has no regex of its own for.
+    assert_eq!(bot.subscribers, Some(7));
+
+    Ok(())
+}
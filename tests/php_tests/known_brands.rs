@@ -0,0 +1,173 @@
+use anyhow::Result;
+
+use std::sync::Arc;
+
+use rust_device_detector::device_detector::DeviceDetector;
+use rust_device_detector::known_brands::AvailableBrands;
+use rust_device_detector::rule_database::{RuleDatabase, UnknownBrand};
+
+const KIOSKS: &str = r#"
+'sony ericsson':
+  regex: 'SEKiosk'
+  device: 'tablet'
+  model: 'Kiosk'
+Acme:
+  regex: 'AcmeKiosk'
+  device: 'tablet'
+  models:
+    - regex: 'AcmeKiosk 2'
+      model: 'Kiosk 2'
+      brand: 'Acme Labs'
+    - regex: 'AcmeKiosk'
+      model: 'Kiosk'
+"#;
+
+#[test]
+fn test_device_rules_name_known_brands() -> Result<()> {
+    let brands = AvailableBrands::default();
+
+    let mut files = vec!["regexes/vendorfragments.yml".to_owned()];
+    for entry in std::fs::read_dir("regexes/device")? {
+        files.push(entry?.path().to_string_lossy().into_owned());
+    }
+
+    for file in files {
+        let rules: serde_yaml::Mapping = serde_yaml::from_str(&std::fs::read_to_string(&file)?)?;
+        for (brand, rule) in rules {
+            // some brands, eg. 360, are numbers as far as yaml is concerned.
+            let mut names = vec![match brand {
+                serde_yaml::Value::String(brand) => brand,
+                brand => serde_yaml::to_string(&brand)?.trim().to_owned(),
+            }];
+            for model in rule["models"].as_sequence().into_iter().flatten() {
+                names.extend(model["brand"].as_str().map(str::to_owned));
+            }
+
+            for name in names.into_iter().filter(|name| name != "Unknown") {
+                assert_eq!(
+                    brands.search_by_name(&name).map(|x| x.name.as_str()),
+                    Some(name.as_str()),
+                    "{} in {}",
+                    name,
+                    file
+                );
+            }
+        }
+    }
+
+    assert!(RuleDatabase::embedded().unknown_brands().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_fixture_brands_are_known() -> Result<()> {
+    // the brands upstream expects, independent of the rules the list was taken from.
+    let brands = AvailableBrands::default();
+
+    let mut checked = 0;
+    for entry in std::fs::read_dir("tests/data/fixtures")? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("yml") {
+            continue;
+        }
+
+        let fixtures: Vec<serde_yaml::Value> =
+            serde_yaml::from_str(&std::fs::read_to_string(&path)?)?;
+        for fixture in fixtures {
+            let name = match &fixture["device"]["brand"] {
+                serde_yaml::Value::String(name) if !name.is_empty() => name.clone(),
+                serde_yaml::Value::Number(name) => name.to_string(),
+                _ => continue,
+            };
+
+            assert_eq!(
+                brands.search_by_name(&name).map(|x| x.name.as_str()),
+                Some(name.as_str()),
+                "{} in {}",
+                name,
+                path.display()
+            );
+            checked += 1;
+        }
+    }
+    assert!(checked > 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_search_by_name() {
+    let brands = AvailableBrands::default();
+    assert_eq!(
+        brands
+            .search_by_name("sony ericsson")
+            .map(|x| x.name.as_str()),
+        Some("Sony Ericsson")
+    );
+    assert_eq!(
+        brands.search_by_name("COOCAA").map(|x| x.name.as_str()),
+        Some("coocaa")
+    );
+    assert_eq!(
+        brands.search_by_name("türksat").map(|x| x.name.as_str()),
+        Some("Türksat")
+    );
+    assert!(brands.search_by_name("Acme").is_none());
+
+    // by_name only ignores case.
+    assert_eq!(
+        brands.by_name("sony ericsson").map(|x| x.name.as_str()),
+        Some("Sony Ericsson")
+    );
+    assert!(brands.by_name("SonyEricsson").is_none());
+    assert!(brands.search_by_name("SonyEricsson").is_some());
+}
+
+#[test]
+fn test_brands_are_normalized() -> Result<()> {
+    let database = RuleDatabase::builder()
+        .overlay("device/mobiles.yml", "kiosks", KIOSKS)
+        .build()?;
+
+    assert_eq!(
+        database.unknown_brands(),
+        [
+            UnknownBrand {
+                file: "device/mobiles.yml",
+                overlay: Some("kiosks".to_owned()),
+                rule: 0,
+                brand: "sony ericsson".to_owned(),
+            },
+            UnknownBrand {
+                file: "device/mobiles.yml",
+                overlay: Some("kiosks".to_owned()),
+                rule: 1,
+                brand: "Acme".to_owned(),
+            },
+            UnknownBrand {
+                file: "device/mobiles.yml",
+                overlay: Some("kiosks".to_owned()),
+                rule: 1,
+                brand: "Acme Labs".to_owned(),
+            },
+        ]
+    );
+
+    let detector = DeviceDetector::with_database(Arc::new(database));
+    let brand = |model: &str| -> Result<Option<String>> {
+        let ua = format!("Mozilla/5.0 (Linux; Android 10; {}) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Safari/537.36", model);
+        Ok(detector
+            .parse(&ua, None)?
+            .get_known_device()
+            .and_then(|known| known.device.as_ref())
+            .and_then(|device| device.brand.clone()))
+    };
+
+    assert_eq!(brand("SEKiosk")?.as_deref(), Some("Sony Ericsson"));
+    // brands upstream doesn't know of are left as they are.
+    assert_eq!(brand("AcmeKiosk")?.as_deref(), Some("Acme"));
+    assert_eq!(brand("AcmeKiosk 2")?.as_deref(), Some("Acme Labs"));
+
+    Ok(())
+}
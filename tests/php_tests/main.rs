@@ -3,8 +3,28 @@
 // #[global_allocator]
 //static GLOBAL: &StatsAlloc<System> = &INSTRUMENTED_SYSTEM;
 
+mod accuracy;
+mod automation;
+mod batch;
+mod bot_catalog;
 mod bots;
+mod browser_family;
+mod cache;
 mod client_hints;
+mod client_urls;
+mod consistency;
+mod error;
+mod explain;
+mod feeds;
 mod fixtures;
+mod known_brands;
+mod options;
+mod overlay;
 mod parser;
+mod pipeline;
+mod prefilter;
+mod provenance;
+mod rule_database;
+mod short_names;
 mod utils;
+mod verify;
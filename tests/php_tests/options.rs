@@ -0,0 +1,115 @@
+use anyhow::Result;
+
+use rust_device_detector::device_detector::{DeviceDetector, ParserFamily, VersionTruncation};
+
+const BOT: &str = "Googlebot/2.1 (+http://www.google.com/bot.html)";
+const CHROME_MAC: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.5735.199 Safari/537.36";
+const SPOTIFY: &str = "Spotify/8.6.72 iOS/13.5.1 (iPhone9,2)";
+
+#[test]
+fn test_skip_bot_detection() -> Result<()> {
+    let detector = DeviceDetector::builder().skip_bot_detection(true).build();
+
+    let res = detector.parse(BOT, None)?;
+    assert!(!res.is_bot());
+    assert!(res.get_known_device().is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_discard_bot_information() -> Result<()> {
+    let detector = DeviceDetector::builder()
+        .discard_bot_information(true)
+        .build();
+
+    let res = detector.parse(BOT, None)?;
+    assert!(res.is_bot());
+    assert!(res.get_bot().is_none());
+    assert_eq!(res.to_value(), serde_json::json!({ "bot": true }));
+
+    // everything else is detected as usual.
+    assert!(!detector.parse(CHROME_MAC, None)?.is_bot());
+
+    Ok(())
+}
+
+#[test]
+fn test_version_truncation() -> Result<()> {
+    let versions = |truncation| -> Result<[Option<String>; 3]> {
+        let detector = DeviceDetector::builder()
+            .version_truncation(truncation)
+            .build();
+        let res = detector.parse(CHROME_MAC, None)?;
+        let known = res.get_known_device().expect("known device");
+        let client = known.client.as_ref().expect("client");
+
+        Ok([
+            client.version.clone(),
+            client.engine_version.clone(),
+            known.os.as_ref().and_then(|os| os.version.clone()),
+        ])
+    };
+
+    let expected = |client: &str, os: &str| {
+        [
+            Some(client.to_owned()),
+            Some(client.to_owned()),
+            Some(os.to_owned()),
+        ]
+    };
+
+    assert_eq!(
+        versions(VersionTruncation::None)?,
+        expected("114.0.5735.199", "10.15.7")
+    );
+    assert_eq!(
+        versions(VersionTruncation::Patch)?,
+        expected("114.0.5735", "10.15.7")
+    );
+    assert_eq!(
+        versions(VersionTruncation::Minor)?,
+        expected("114.0", "10.15")
+    );
+    assert_eq!(versions(VersionTruncation::Major)?, expected("114", "10"));
+
+    Ok(())
+}
+
+#[test]
+fn test_disabled_parsers() -> Result<()> {
+    let client_name = |detector: &DeviceDetector| -> Result<Option<String>> {
+        Ok(detector
+            .parse(SPOTIFY, None)?
+            .get_known_device()
+            .and_then(|known| known.client.as_ref())
+            .map(|client| client.name.clone()))
+    };
+    let model = |detector: &DeviceDetector| -> Result<Option<String>> {
+        Ok(detector
+            .parse(SPOTIFY, None)?
+            .get_known_device()
+            .and_then(|known| known.device.as_ref())
+            .and_then(|device| device.model.clone()))
+    };
+
+    let detector = DeviceDetector::new();
+    assert_eq!(client_name(&detector)?.as_deref(), Some("Spotify"));
+    assert_eq!(model(&detector)?.as_deref(), Some("iPhone 7 Plus"));
+
+    let detector = DeviceDetector::builder()
+        .disable_parser(ParserFamily::MobileApp)
+        .disable_parser(ParserFamily::Mobile)
+        .build();
+    assert_ne!(client_name(&detector)?.as_deref(), Some("Spotify"));
+    assert_eq!(model(&detector)?, None);
+
+    // and back on again.
+    let detector = DeviceDetector::builder()
+        .disable_parser(ParserFamily::MobileApp)
+        .enable_parser(ParserFamily::MobileApp)
+        .build();
+    assert_eq!(client_name(&detector)?.as_deref(), Some("Spotify"));
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use anyhow::Result;
+
+use std::sync::Arc;
+
+use rust_device_detector::device_detector::DeviceDetector;
+use rust_device_detector::error::DetectError;
+use rust_device_detector::rule_database::RuleDatabase;
+use rust_device_detector::trace::{Part, TraceStep};
+
+const PARTNER_BOTS: &str = r#"
+- regex: 'AcmePartnerBot(?:/(\d+[.\d]+))?'
+  name: 'Acme Partner Bot'
+  category: 'Crawler'
+  url: 'https://acme.example/bot'
+"#;
+
+const KIOSKS: &str = r#"
+Acme:
+  regex: 'AcmeKiosk'
+  device: 'tablet'
+  model: 'Kiosk'
+"#;
+
+#[test]
+fn test_overlays() -> Result<()> {
+    let database = RuleDatabase::builder()
+        .overlay("bots.yml", "partner bots", PARTNER_BOTS)
+        .overlay("device/mobiles.yml", "kiosks", KIOSKS)
+        .build()?;
+    let detector = DeviceDetector::with_database(Arc::new(database));
+
+    let explanation = detector.explain("AcmePartnerBot/1.2", None)?;
+    let bot = explanation.detection.get_bot().expect("bot");
+    assert_eq!(bot.name, "Acme Partner Bot");
+    assert_eq!(
+        explanation.trace,
+        [TraceStep::Rule {
+            part: Part::Bot,
+            file: "bots.yml".to_owned(),
+            overlay: Some("partner bots".to_owned()),
+            rule: 0,
+            model: None,
+        }]
+    );
+
+    let ua = "Mozilla/5.0 (Linux; Android 10; AcmeKiosk) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Safari/537.36";
+    let explanation = detector.explain(ua, None)?;
+    let device = explanation
+        .detection
+        .get_known_device()
+        .and_then(|known| known.device.as_ref())
+        .expect("device");
+    assert_eq!(device.brand.as_deref(), Some("Acme"));
+    assert_eq!(device.model.as_deref(), Some("Kiosk"));
+    assert!(explanation.trace.contains(&TraceStep::Rule {
+        part: Part::Device,
+        file: "device/mobiles.yml".to_owned(),
+        overlay: Some("kiosks".to_owned()),
+        rule: 0,
+        model: Some(0),
+    }));
+
+    // built in rules are still there, and keep their own indices.
+    let explanation = detector.explain("Googlebot/2.1 (+http://www.google.com/bot.html)", None)?;
+    assert_eq!(
+        explanation.detection.get_bot().map(|bot| bot.name.as_str()),
+        Some("Googlebot")
+    );
+    assert!(matches!(
+        explanation.trace.as_slice(),
+        [TraceStep::Rule { file, overlay: None, .. }] if file == "bots.yml"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_overlays_take_precedence() -> Result<()> {
+    let ua = "Spotify/8.6.72 iOS/13.5.1 (iPhone9,2)";
+
+    let internal = r#"
+- regex: 'Spotify/(\d+[.\d]+)'
+  name: 'Spotify Internal'
+  version: '$1'
+"#;
+    let later = r#"
+- regex: 'Spotify/(\d+[.\d]+)'
+  name: 'Spotify Later'
+  version: '$1'
+"#;
+
+    let database = RuleDatabase::builder()
+        .overlay("client/mobile_apps.yml", "internal", internal)
+        .overlay("client/mobile_apps.yml", "later", later)
+        .build()?;
+    let detector = DeviceDetector::with_database(Arc::new(database));
+
+    // overlays are checked in the order they were added, all before the built in rules.
+    let detection = detector.parse(ua, None)?;
+    let client = detection
+        .get_known_device()
+        .and_then(|known| known.client.as_ref())
+        .expect("client");
+    assert_eq!(client.name, "Spotify Internal");
+    assert_eq!(client.version.as_deref(), Some("8.6.72"));
+
+    let detection = DeviceDetector::new().parse(ua, None)?;
+    let client = detection
+        .get_known_device()
+        .and_then(|known| known.client.as_ref())
+        .expect("client");
+    assert_eq!(client.name, "Spotify");
+
+    Ok(())
+}
+
+#[test]
+fn test_overlay_errors() -> Result<()> {
+    let err = RuleDatabase::builder()
+        .overlay("bots.yaml", "typo", PARTNER_BOTS)
+        .build()
+        .unwrap_err();
+    assert!(matches!(&err, DetectError::RuleDatabase { file, .. } if file == "bots.yaml"));
+
+    // hints map names rather than being a list of rules, there is nothing to prepend to.
+    assert!(RuleDatabase::builder()
+        .overlay("client/hints/apps.yml", "hints", "com.acme.app: 'Acme'")
+        .build()
+        .is_err());
+
+    let err = RuleDatabase::builder()
+        .overlay("bots.yml", "partner bots", "- regex: [")
+        .build()
+        .unwrap_err();
+    assert!(matches!(&err, DetectError::RuleDatabase { file, .. } if file == "partner bots"));
+
+    // a broken rule is reported by its index within the overlay.
+    let broken = format!(
+        "{}\n- regex: 'Broken(Bot'\n  name: 'Broken Bot'\n",
+        PARTNER_BOTS
+    );
+    let err = RuleDatabase::builder()
+        .overlay("bots.yml", "partner bots", broken)
+        .build()?
+        .validate()
+        .unwrap_err();
+    assert!(
+        matches!(
+            &err,
+            DetectError::RuleDatabase { file, rule: Some(1), .. } if file == "partner bots"
+        ),
+        "{:?}",
+        err
+    );
+
+    let err = RuleDatabase::builder()
+        .overlay_file("bots.yml", "/nonexistent/partner_bots.yml")
+        .build()
+        .unwrap_err();
+    assert_eq!(err.code(), 3);
+
+    Ok(())
+}
@@ -15,7 +15,7 @@ fn test_parser_browsers() -> Result<()> {
     };
     let mut cases: Value = serde_yaml::from_reader(BufReader::new(file))?;
     let cases = cases.as_sequence_mut().expect("sequence");
-    for (i, case) in cases.into_iter().enumerate() {
+    for (i, case) in cases.iter_mut().enumerate() {
         basic(i + 1, case).expect("basic test");
     }
     Ok(())
@@ -23,7 +23,7 @@ fn test_oss() -> Result<()> {
         let mut cases: Value = serde_yaml::from_reader(std::io::BufReader::new(file))?;
         let cases = cases.as_sequence_mut().expect("sequence");
         let mut failures = 0;
-        for (i, case) in cases.into_iter().enumerate() {
+        for (i, case) in cases.iter_mut().enumerate() {
             let ua = case["user_agent"].as_str().unwrap_or("").to_owned();
             let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                 basic(i + 1, case).expect("basic test");
@@ -119,5 +119,17 @@ fn basic(idx: usize, value: &mut Value) -> Result<()> {
         ua
     );
 
+    let test_os_short_name: Option<&str> = test_os["short_name"].as_str();
+    let dd_os_short_name: Option<&str> = dd_os.and_then(|os| os.short_name.as_deref());
+
+    assert!(
+        test_os_short_name == dd_os_short_name,
+        "os short name test case: {}\n code: {:?}\n test: {:?} ua: {}",
+        idx,
+        dd_os,
+        test_os,
+        ua
+    );
+
     Ok(())
 }
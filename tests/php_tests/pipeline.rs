@@ -0,0 +1,202 @@
+use anyhow::Result;
+
+use rust_device_detector::client_hints::ClientHint;
+
+use rust_device_detector::device_detector::{DeviceDetector, KnownDevice, ParserFamily};
+use rust_device_detector::error::Result as DetectResult;
+use rust_device_detector::parsers::client::{Client, ClientType};
+use rust_device_detector::parsers::device::{Device, DeviceType};
+use rust_device_detector::pipeline::{ParseInput, Parser, Position, PostProcessor};
+use rust_device_detector::trace::{Part, Reason, TraceStep};
+
+const SPOTIFY: &str = "Spotify/8.6.72 iOS/13.5.1 (iPhone9,2)";
+const CHROME: &str = "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Mobile Safari/537.36";
+
+// claims every user agent mentioning Spotify.
+struct InternalApp;
+
+impl Parser for InternalApp {
+    type Output = Client;
+
+    fn name(&self) -> &str {
+        "internal app"
+    }
+
+    fn parse(&self, input: &ParseInput<'_>) -> DetectResult<Option<Client>> {
+        Ok(input.ua.contains("Spotify").then(|| {
+            Client::new(
+                "Internal Player",
+                Some("1.0".to_owned()),
+                ClientType::MediaPlayer,
+            )
+        }))
+    }
+}
+
+struct Kiosk;
+
+impl Parser for Kiosk {
+    type Output = Device;
+
+    fn name(&self) -> &str {
+        "kiosk"
+    }
+
+    fn parse(&self, input: &ParseInput<'_>) -> DetectResult<Option<Device>> {
+        // device parsers see the client, which is detected first.
+        let is_chrome = input
+            .client
+            .is_some_and(|client| client.name == "Chrome Mobile");
+        Ok((is_chrome && input.ua.contains("Android 10")).then(|| {
+            Device::new(
+                Some(DeviceType::SmartDisplay),
+                Some("Acme".to_owned()),
+                Some("Kiosk".to_owned()),
+            )
+        }))
+    }
+}
+
+struct RenameBrand;
+
+impl PostProcessor for RenameBrand {
+    fn process(
+        &self,
+        _ua: &str,
+        _client_hints: Option<&ClientHint>,
+        known: &mut KnownDevice,
+    ) -> DetectResult<()> {
+        if let Some(device) = &mut known.device {
+            if device.brand.as_deref() == Some("Apple") {
+                device.brand = Some("Apple Inc.".to_owned());
+            }
+        }
+        Ok(())
+    }
+}
+
+fn client_name(detector: &DeviceDetector, ua: &str) -> Result<Option<String>> {
+    Ok(detector
+        .parse(ua, None)?
+        .get_known_device()
+        .and_then(|known| known.client.as_ref())
+        .map(|client| client.name.clone()))
+}
+
+#[test]
+fn test_client_parser_positions() -> Result<()> {
+    assert_eq!(
+        client_name(&DeviceDetector::new(), SPOTIFY)?.as_deref(),
+        Some("Spotify")
+    );
+
+    // spotify is a mobile app, so anything inserted ahead of mobile apps wins.
+    for position in [
+        Position::First,
+        Position::Before(ParserFamily::MobileApp),
+        Position::After(ParserFamily::FeedReader),
+    ] {
+        let detector = DeviceDetector::builder()
+            .insert_client_parser(position, InternalApp)?
+            .build();
+        assert_eq!(
+            client_name(&detector, SPOTIFY)?.as_deref(),
+            Some("Internal Player"),
+            "{:?}",
+            position
+        );
+    }
+
+    for position in [Position::Last, Position::After(ParserFamily::MobileApp)] {
+        let detector = DeviceDetector::builder()
+            .insert_client_parser(position, InternalApp)?
+            .build();
+        assert_eq!(
+            client_name(&detector, SPOTIFY)?.as_deref(),
+            Some("Spotify"),
+            "{:?}",
+            position
+        );
+    }
+
+    // turning the built in parser off leaves the inserted one.
+    let detector = DeviceDetector::builder()
+        .insert_client_parser(Position::After(ParserFamily::MobileApp), InternalApp)?
+        .disable_parser(ParserFamily::MobileApp)
+        .build();
+    assert_eq!(
+        client_name(&detector, SPOTIFY)?.as_deref(),
+        Some("Internal Player")
+    );
+
+    let trace = detector.explain(SPOTIFY, None)?.trace;
+    assert!(trace.contains(&TraceStep::Parser {
+        part: Part::Client,
+        name: "internal app".to_owned(),
+    }));
+
+    Ok(())
+}
+
+#[test]
+fn test_device_parser() -> Result<()> {
+    let detector = DeviceDetector::builder()
+        .insert_device_parser(Position::First, Kiosk)?
+        .build();
+
+    let detection = detector.parse(CHROME, None)?;
+    let device = detection
+        .get_known_device()
+        .and_then(|known| known.device.as_ref())
+        .expect("device");
+    assert_eq!(device.brand.as_deref(), Some("Acme"));
+    assert_eq!(device.model.as_deref(), Some("Kiosk"));
+    assert_eq!(device.device_type, Some(DeviceType::SmartDisplay));
+
+    // other user agents carry on with the built in parsers.
+    let detection = detector.parse(SPOTIFY, None)?;
+    let device = detection
+        .get_known_device()
+        .and_then(|known| known.device.as_ref())
+        .expect("device");
+    assert_eq!(device.model.as_deref(), Some("iPhone 7 Plus"));
+
+    // a position among the client parsers makes no sense for a device parser.
+    let err = DeviceDetector::builder()
+        .insert_device_parser(Position::Before(ParserFamily::Browser), Kiosk)
+        .err()
+        .expect("error");
+    assert_eq!(err.name(), "invalid_input");
+    assert!(DeviceDetector::builder()
+        .insert_client_parser(Position::After(ParserFamily::Mobile), InternalApp)
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_post_processor() -> Result<()> {
+    let detector = DeviceDetector::builder().post_process(RenameBrand).build();
+
+    let detection = detector.parse(SPOTIFY, None)?;
+    let device = detection
+        .get_known_device()
+        .and_then(|known| known.device.as_ref())
+        .expect("device");
+    assert_eq!(device.brand.as_deref(), Some("Apple Inc."));
+
+    let trace = detector.explain(SPOTIFY, None)?.trace;
+    assert!(trace.contains(&TraceStep::Override {
+        part: Part::Device,
+        field: "brand",
+        value: Some("Apple Inc.".to_owned()),
+        reason: Reason::PostProcessor,
+    }));
+
+    // bots are left alone.
+    assert!(detector
+        .parse("Googlebot/2.1 (+http://www.google.com/bot.html)", None)?
+        .is_bot());
+
+    Ok(())
+}
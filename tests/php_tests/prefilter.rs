@@ -0,0 +1,60 @@
+use anyhow::Result;
+
+use rust_device_detector::device_detector::DeviceDetector;
+
+fn brand(detector: &DeviceDetector, ua: &str) -> Result<Option<String>> {
+    let res = detector.parse(ua, None)?;
+    Ok(res
+        .get_known_device()
+        .and_then(|dev| dev.device.as_ref())
+        .and_then(|dev| dev.brand.clone()))
+}
+
+// Brands are tried in file order and the first match wins, the prefilter must not
+// change which one that is.
+#[test]
+fn test_first_match_order() -> Result<()> {
+    let acer = r#"
+Acer:
+  regex: 'Frob'
+  device: 'smartphone'
+  model: 'Frob'
+"#;
+    let alcatel = r#"
+Alcatel:
+  regex: 'Frob X1'
+  device: 'smartphone'
+  model: 'X1'
+"#;
+
+    let ua = "Mozilla/5.0 (Linux; Android 10; Frob X1) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Mobile Safari/537.36";
+
+    let detector =
+        DeviceDetector::from_sources([("device/mobiles.yml", format!("{}{}", acer, alcatel))])?;
+    assert_eq!(brand(&detector, ua)?.as_deref(), Some("Acer"));
+
+    let detector =
+        DeviceDetector::from_sources([("device/mobiles.yml", format!("{}{}", alcatel, acer))])?;
+    assert_eq!(brand(&detector, ua)?.as_deref(), Some("Alcatel"));
+
+    Ok(())
+}
+
+// Case insensitive matching covers a few non ascii characters too, such as the
+// kelvin sign for k.
+#[test]
+fn test_non_ascii_user_agent() -> Result<()> {
+    let mobiles = r#"
+Acer:
+  regex: 'Kestrel'
+  device: 'smartphone'
+  model: 'Kestrel'
+"#;
+
+    let detector = DeviceDetector::from_sources([("device/mobiles.yml", mobiles)])?;
+
+    let ua = "Mozilla/5.0 (Linux; Android 10; \u{212A}estrel) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Mobile Safari/537.36";
+    assert_eq!(brand(&detector, ua)?.as_deref(), Some("Acer"));
+
+    Ok(())
+}
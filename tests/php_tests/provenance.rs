@@ -0,0 +1,78 @@
+use anyhow::Result;
+
+use rust_device_detector::device_detector::DeviceDetector;
+use rust_device_detector::trace::Source;
+
+const SPOTIFY: &str = "Spotify/8.6.72 iOS/13.5.1 (iPhone9,2)";
+
+#[test]
+fn test_provenance() -> Result<()> {
+    let detector = DeviceDetector::builder().record_provenance(true).build();
+
+    let detection = detector.parse(SPOTIFY, None)?;
+    let provenance = detection
+        .get_known_device()
+        .and_then(|known| known.provenance.as_ref())
+        .expect("provenance");
+    assert_eq!(provenance.client.get("name"), Some(&Source::Ua));
+    assert_eq!(provenance.device.get("model"), Some(&Source::Ua));
+    assert_eq!(provenance.os.get("version"), Some(&Source::Ua));
+    // only fields that were detected.
+    assert_eq!(provenance.client.get("engine"), None);
+
+    let ua = "Mozilla/5.0 (Windows NT 6.1; WOW64; Trident/7.0; MDDRJS; rv:11.0) like Gecko";
+    let value = detector.parse(ua, None)?.to_value();
+    assert_eq!(value["provenance"]["device"]["brand"], "vendor_fragment");
+    assert_eq!(value["provenance"]["device"]["type"], "heuristic");
+    assert_eq!(value["provenance"]["client"]["name"], "ua");
+
+    Ok(())
+}
+
+#[test]
+fn test_provenance_of_reduced_user_agents() -> Result<()> {
+    let detector = DeviceDetector::builder().record_provenance(true).build();
+
+    let ua = "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36";
+    let headers = vec![
+        ("sec-ch-ua-model".to_owned(), r#""SM-G991B""#.to_owned()),
+        ("sec-ch-ua-platform".to_owned(), r#""Android""#.to_owned()),
+        (
+            "sec-ch-ua-platform-version".to_owned(),
+            r#""13.0.0""#.to_owned(),
+        ),
+        (
+            "sec-ch-ua-full-version-list".to_owned(),
+            r#""Not_A Brand";v="8.0.0.0", "Chromium";v="120.0.6099.71", "Google Chrome";v="120.0.6099.71""#
+                .to_owned(),
+        ),
+    ];
+
+    let value = detector.parse(ua, Some(headers))?.to_value();
+    // the model was restored into the user agent from the hints.
+    assert_eq!(value["device"]["model"], "Galaxy S21 5G");
+    assert_eq!(value["provenance"]["device"]["model"], "client_hints");
+    assert_eq!(value["provenance"]["device"]["brand"], "client_hints");
+    assert_eq!(value["provenance"]["os"]["version"], "client_hints");
+    assert_eq!(value["provenance"]["client"]["name"], "ua");
+    assert_eq!(value["provenance"]["client"]["version"], "client_hints");
+
+    Ok(())
+}
+
+#[test]
+fn test_provenance_is_optional() -> Result<()> {
+    let value = DeviceDetector::new().parse(SPOTIFY, None)?.to_value();
+    assert!(value.get("provenance").is_none());
+
+    // recording it leaves explanations as they were.
+    let detector = DeviceDetector::builder().record_provenance(true).build();
+    let explanation = detector.explain(SPOTIFY, None)?;
+    assert_eq!(
+        explanation.trace,
+        DeviceDetector::new().explain(SPOTIFY, None)?.trace
+    );
+    assert!(explanation.detection.to_value().get("provenance").is_some());
+
+    Ok(())
+}
@@ -0,0 +1,228 @@
+use anyhow::Result;
+
+use std::sync::Arc;
+
+use rust_device_detector::device_detector::DeviceDetector;
+use rust_device_detector::rule_database::RuleDatabase;
+
+#[test]
+fn test_from_dir_matches_embedded() -> Result<()> {
+    let embedded = DeviceDetector::new();
+    let from_dir = DeviceDetector::from_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/regexes"))?;
+
+    for ua in [
+        "Googlebot/2.1 (+http://www.google.com/bot.html)",
+        "Spotify/8.6.72 iOS/13.5.1 (iPhone9,2)",
+        "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Mobile Safari/537.36",
+    ] {
+        assert_eq!(
+            embedded.parse(ua, None)?.to_value(),
+            from_dir.parse(ua, None)?.to_value(),
+            "ua: {}",
+            ua
+        );
+    }
+
+    Ok(())
+}
+
+// The embedded database is pre-parsed by build.rs, detections must not differ from the
+// same rules parsed as yaml.
+#[cfg(feature = "precompiled")]
+#[test]
+fn test_precompiled_matches_yaml() -> Result<()> {
+    let precompiled = DeviceDetector::new();
+    let yaml = DeviceDetector::from_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/regexes"))?;
+
+    for fixture in [
+        "bots.yml",
+        "desktop.yml",
+        "smartphone.yml",
+        "tv.yml",
+        "feed_reader.yml",
+    ] {
+        let path = format!(
+            "{}/tests/data/fixtures/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            fixture
+        );
+        let cases: serde_yaml::Value = serde_yaml::from_reader(std::fs::File::open(path)?)?;
+
+        for case in cases.as_sequence().expect("sequence") {
+            let ua = case["user_agent"].as_str().expect("user agent");
+            assert_eq!(
+                precompiled.parse(ua, None)?.to_value(),
+                yaml.parse(ua, None)?.to_value(),
+                "ua: {}",
+                ua
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_from_dir_missing_directory() {
+    assert!(DeviceDetector::from_dir("/nonexistent/regexes").is_err());
+}
+
+#[test]
+fn test_from_sources() -> Result<()> {
+    let bots = r#"
+- regex: 'InternalMonitor'
+  name: 'Internal Monitor'
+  category: 'Site Monitor'
+"#;
+
+    let detector = DeviceDetector::from_sources([("bots.yml", bots)])?;
+
+    let res = detector.parse("InternalMonitor/1.0", None)?;
+    assert_eq!(
+        res.get_bot().map(|bot| bot.name.as_str()),
+        Some("Internal Monitor")
+    );
+
+    // bots.yml was replaced entirely, the rest still comes from the embedded rules.
+    let res = detector.parse("Googlebot/2.1 (+http://www.google.com/bot.html)", None)?;
+    assert!(!res.is_bot());
+
+    let res = detector.parse("Spotify/8.6.72 iOS/13.5.1 (iPhone9,2)", None)?;
+    assert_eq!(
+        res.get_known_device()
+            .and_then(|dev| dev.client.as_ref())
+            .map(|client| client.name.as_str()),
+        Some("Spotify")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_from_sources_errors() {
+    assert!(DeviceDetector::from_sources([("bots.yaml", "")]).is_err());
+    assert!(DeviceDetector::from_sources([("bots.yml", "- regex: [")]).is_err());
+}
+
+#[test]
+fn test_databases_side_by_side() -> Result<()> {
+    let bots = r#"
+- regex: 'Googlebot'
+  name: 'Renamed Googlebot'
+"#;
+
+    let current = DeviceDetector::new();
+    let candidate =
+        DeviceDetector::with_database(Arc::new(RuleDatabase::from_sources([("bots.yml", bots)])?));
+
+    let ua = "Googlebot/2.1 (+http://www.google.com/bot.html)";
+    assert_eq!(
+        current
+            .parse(ua, None)?
+            .get_bot()
+            .map(|bot| bot.name.as_str()),
+        Some("Googlebot")
+    );
+    assert_eq!(
+        candidate
+            .parse(ua, None)?
+            .get_bot()
+            .map(|bot| bot.name.as_str()),
+        Some("Renamed Googlebot")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_embedded_database_is_shared() {
+    let first = DeviceDetector::new();
+    let second = DeviceDetector::new();
+
+    assert!(Arc::ptr_eq(first.database(), second.database()));
+}
+
+#[test]
+fn test_database_dropped_with_last_detector() -> Result<()> {
+    let database = Arc::new(RuleDatabase::from_sources([("bots.yml", "[]")])?);
+    let weak = Arc::downgrade(&database);
+
+    let first = DeviceDetector::with_database(database.clone());
+    let second = DeviceDetector::with_database(database);
+
+    drop(first);
+    assert!(weak.upgrade().is_some());
+
+    drop(second);
+    assert!(weak.upgrade().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_validate() -> Result<()> {
+    RuleDatabase::embedded().validate()?;
+
+    // regexes are compiled on first use, so a broken one only shows up on validation.
+    let bots = r#"
+- regex: 'Broken(Bot'
+  name: 'Broken Bot'
+"#;
+    let database = RuleDatabase::from_sources([("bots.yml", bots)])?;
+    let err = database.validate().unwrap_err();
+    assert!(format!("{:#}", err).contains("bots.yml rule 0"));
+
+    Ok(())
+}
+
+#[test]
+fn test_rebuild_with_database() -> Result<()> {
+    let bots = r#"
+- regex: 'Frobnicator'
+  name: 'Frobnicator'
+"#;
+
+    let detector = DeviceDetector::new();
+    let rebuilt =
+        detector.rebuild_with_database(Arc::new(RuleDatabase::from_sources([("bots.yml", bots)])?));
+
+    assert!(!detector.parse("Frobnicator/1.0", None)?.is_bot());
+    assert!(rebuilt.parse("Frobnicator/1.0", None)?.is_bot());
+
+    Ok(())
+}
+
+#[test]
+fn test_backtracking_rules() -> Result<()> {
+    let bots = r#"
+- regex: 'PlainMonitor'
+  name: 'Plain Monitor'
+- regex: 'PickyMonitor(?!Beta)'
+  name: 'Picky Monitor'
+"#;
+
+    let database = Arc::new(RuleDatabase::from_sources([("bots.yml", bots)])?);
+
+    let rules = database.backtracking_rules()?;
+    let bots: Vec<_> = rules
+        .iter()
+        .filter(|rule| rule.file == "bots.yml")
+        .collect();
+    assert_eq!(bots.len(), 1);
+    assert_eq!(bots[0].rule, 1);
+    assert!(bots[0].pattern.contains("PickyMonitor(?!Beta)"));
+
+    // rules on either engine still detect the same way.
+    let detector = DeviceDetector::with_database(database);
+    let name = |ua| -> Result<Option<String>> {
+        Ok(detector
+            .parse(ua, None)?
+            .get_bot()
+            .map(|bot| bot.name.clone()))
+    };
+    assert_eq!(name("PlainMonitor/1.0")?.as_deref(), Some("Plain Monitor"));
+    assert_eq!(name("PickyMonitor/1.0")?.as_deref(), Some("Picky Monitor"));
+    assert_eq!(name("PickyMonitorBeta/1.0")?, None);
+
+    Ok(())
+}
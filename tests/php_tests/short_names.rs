@@ -0,0 +1,71 @@
+use anyhow::Result;
+
+use rust_device_detector::device_detector::DeviceDetector;
+use rust_device_detector::known_browsers::AvailableBrowsers;
+use rust_device_detector::known_oss::AvailableOSs;
+
+#[test]
+fn test_short_name_tables() {
+    let browsers = AvailableBrowsers::default();
+    assert_eq!(
+        browsers.by_short("CH").map(|x| x.name.as_str()),
+        Some("Chrome")
+    );
+    assert_eq!(
+        browsers
+            .by_name("Chrome Mobile")
+            .map(|x| x.short_name.as_str()),
+        Some("CM")
+    );
+    assert!(browsers.by_short("ch").is_none());
+    // by_name only ignores case, search_by_name ignores spaces and a "browser" suffix.
+    assert!(browsers.by_name("chrome mobile").is_some());
+    assert!(browsers.by_name("ChromeMobile").is_none());
+    assert!(browsers.search_by_name("ChromeMobile").is_some());
+
+    let oss = AvailableOSs::default();
+    assert_eq!(
+        oss.by_short("AND").map(|x| x.name.as_str()),
+        Some("Android")
+    );
+    assert_eq!(
+        oss.by_name("iOS").map(|x| x.short_name.as_str()),
+        Some("IOS")
+    );
+    assert!(oss.by_short("XXX").is_none());
+}
+
+#[test]
+fn test_short_names() -> Result<()> {
+    let detector = DeviceDetector::new();
+
+    let ua = "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.0.0 Mobile Safari/537.36";
+    let detection = detector.parse(ua, None)?;
+    let known = detection.get_known_device().expect("known device");
+    assert_eq!(
+        known.client.as_ref().and_then(|x| x.short_name.as_deref()),
+        Some("CM")
+    );
+    assert_eq!(
+        known.os.as_ref().and_then(|x| x.short_name.as_deref()),
+        Some("AND")
+    );
+
+    let value = detection.to_value();
+    assert_eq!(value["client"]["short_name"], "CM");
+    assert_eq!(value["os"]["short_name"], "AND");
+
+    // upstream only has short codes for browsers.
+    let detection = detector.parse("Spotify/8.6.72 iOS/13.5.1 (iPhone9,2)", None)?;
+    let known = detection.get_known_device().expect("known device");
+    assert_eq!(
+        known.client.as_ref().map(|x| x.short_name.clone()),
+        Some(None)
+    );
+    assert_eq!(
+        known.os.as_ref().and_then(|x| x.short_name.as_deref()),
+        Some("IOS")
+    );
+
+    Ok(())
+}
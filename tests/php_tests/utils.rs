@@ -11,7 +11,7 @@ use once_cell::sync::Lazy;
 use rust_device_detector::client_hints::ClientHint;
 use rust_device_detector::device_detector::DeviceDetector;
 
-pub(crate) static DD: Lazy<DeviceDetector> = Lazy::new(|| DeviceDetector::new());
+pub(crate) static DD: Lazy<DeviceDetector> = Lazy::new(DeviceDetector::new);
 
 // use stats_alloc::{Stats, INSTRUMENTED_SYSTEM};
 // pub fn memory_test(f: &dyn Fn() -> Result<()>) -> Result<Stats> {
@@ -64,7 +64,7 @@ pub fn client_hint_mock(fields: &Mapping) -> Result<ClientHint> {
 
     for (key, value) in fields {
         let key = key.as_str().expect("header name or mock field name");
-        if !MOCK_HEADERS.iter().any(|x| *x == key) {
+        if !MOCK_HEADERS.contains(&key) {
             // Skip null header values (e.g., Sec-CH-UA-Model: null means model is not set)
             if let Some(v) = value.as_str() {
                 normal_fields.push((
@@ -82,7 +82,7 @@ pub fn client_hint_mock(fields: &Mapping) -> Result<ClientHint> {
 
     for (key, value) in fields {
         let key = key.as_str().expect("header name or mock field name");
-        if MOCK_HEADERS.iter().any(|x| *x == key) {
+        if MOCK_HEADERS.contains(&key) {
             match key {
                 "arch" | "architecture" => {
                     client_hints.architecture =
@@ -128,11 +128,7 @@ pub fn client_hint_mock(fields: &Mapping) -> Result<ClientHint> {
                         client_hints.mobile = value.as_bool().expect("mobile");
                     } else {
                         let res: &str = value.as_str().expect("mobile field as a string");
-                        if res == "1" {
-                            client_hints.mobile = true;
-                        } else {
-                            client_hints.mobile = false;
-                        }
+                        client_hints.mobile = res == "1";
                     }
                 }
 
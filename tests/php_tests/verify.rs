@@ -0,0 +1,233 @@
+use anyhow::Result;
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use rust_device_detector::device_detector::DeviceDetector;
+use rust_device_detector::error::{DetectError, Result as DetectResult};
+use rust_device_detector::verify::{IpRanges, Resolver, ReverseDns, Verification};
+
+const GOOGLEBOT: &str = "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+const BINGBOT: &str = "Mozilla/5.0 (compatible; bingbot/2.0; +http://www.bing.com/bingbot.htm)";
+const GPTBOT: &str = "Mozilla/5.0 AppleWebKit/537.36 (KHTML, like Gecko; compatible; GPTBot/1.2; +https://openai.com/gptbot)";
+// both detected as Googlebot, though neither crawls from googlebot's ranges.
+const ADSBOT: &str = "AdsBot-Google (+http://www.google.com/adsbot.html)";
+const FEEDFETCHER: &str = "Feedfetcher-Google; (+http://www.google.com/feedfetcher.html; 19 subscribers; feed-id=13965549748850348809)";
+
+// trimmed down copies of the files google and openai publish.
+const GOOGLE_RANGES: &str = r#"{
+  "creationTime": "2024-07-08T15:46:02.000000",
+  "prefixes": [
+    {"ipv6Prefix": "2001:4860:4801:10::/64"},
+    {"ipv4Prefix": "66.249.64.0/27"},
+    {"ipv4Prefix": "66.249.66.0/27"}
+  ]
+}"#;
+
+const GOOGLE_SPECIAL_CRAWLER_RANGES: &str = r#"{
+  "creationTime": "2024-07-08T15:46:02.000000",
+  "prefixes": [
+    {"ipv4Prefix": "66.249.90.64/27"}
+  ]
+}"#;
+
+const OPENAI_RANGES: &str = r#"{
+  "creationTime": "2024-11-21T00:00:00.000000",
+  "prefixes": [
+    {"ipv4Prefix": "20.15.240.64/28"}
+  ]
+}"#;
+
+// a local stand in for dns.
+#[derive(Default)]
+struct StubResolver {
+    ptr: HashMap<IpAddr, Vec<String>>,
+    hosts: HashMap<String, Vec<IpAddr>>,
+}
+
+impl StubResolver {
+    fn host(mut self, ip: &str, host: &str) -> Self {
+        let ip: IpAddr = ip.parse().unwrap();
+        self.ptr.entry(ip).or_default().push(format!("{}.", host));
+        self.hosts.entry(host.to_owned()).or_default().push(ip);
+        self
+    }
+
+    // a ptr record only, as anyone controlling the reverse zone of their ips can add.
+    fn ptr(mut self, ip: &str, host: &str) -> Self {
+        let ip: IpAddr = ip.parse().unwrap();
+        self.ptr.entry(ip).or_default().push(host.to_owned());
+        self
+    }
+}
+
+impl Resolver for StubResolver {
+    fn reverse(&self, ip: IpAddr) -> DetectResult<Vec<String>> {
+        match self.ptr.get(&ip) {
+            Some(hosts) => Ok(hosts.clone()),
+            None if ip.is_loopback() => Err(DetectError::InvalidInput("timed out".to_owned())),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn forward(&self, host: &str) -> DetectResult<Vec<IpAddr>> {
+        Ok(self.hosts.get(host).cloned().unwrap_or_default())
+    }
+}
+
+fn verification(detector: &DeviceDetector, ua: &str, ip: &str) -> Result<Verification> {
+    let detection = detector.parse_with_ip(ua, None, ip.parse()?)?;
+    Ok(detection.get_bot().expect("bot").verification)
+}
+
+#[test]
+fn test_ip_ranges() -> Result<()> {
+    let ranges = IpRanges::default()
+        .with_ranges("Googlebot", GOOGLE_RANGES)?
+        .with_ranges("GPTBot", OPENAI_RANGES)?;
+    let detector = DeviceDetector::builder().verify_bots(ranges).build();
+
+    assert_eq!(
+        verification(&detector, GOOGLEBOT, "66.249.66.1")?,
+        Verification::Verified
+    );
+    assert_eq!(
+        verification(&detector, GOOGLEBOT, "2001:4860:4801:10::1")?,
+        Verification::Verified
+    );
+    assert_eq!(
+        verification(&detector, GOOGLEBOT, "66.249.66.32")?,
+        Verification::Spoofed
+    );
+    assert_eq!(
+        verification(&detector, GOOGLEBOT, "2001:4860:4801:11::1")?,
+        Verification::Spoofed
+    );
+    assert_eq!(
+        verification(&detector, GPTBOT, "20.15.240.79")?,
+        Verification::Verified
+    );
+    assert_eq!(
+        verification(&detector, GPTBOT, "20.15.240.80")?,
+        Verification::Spoofed
+    );
+    // no ranges were given for bing.
+    assert_eq!(
+        verification(&detector, BINGBOT, "157.55.39.1")?,
+        Verification::Unknown
+    );
+    // nor for the other crawlers of Googlebot's rule.
+    assert_eq!(
+        verification(&detector, ADSBOT, "66.249.90.65")?,
+        Verification::Unknown
+    );
+    assert_eq!(
+        verification(&detector, FEEDFETCHER, "66.249.90.65")?,
+        Verification::Unknown
+    );
+
+    // without an ip nothing is verified, and the result shows it.
+    let detection = detector.parse(GOOGLEBOT, None)?;
+    assert_eq!(
+        detection.get_bot().expect("bot").verification,
+        Verification::Unknown
+    );
+    assert_eq!(detection.to_value()["bot"]["verification"], "unknown");
+
+    let detection = detector.parse_with_ip(GOOGLEBOT, None, "66.249.66.1".parse()?)?;
+    assert_eq!(detection.to_value()["bot"]["verification"], "verified");
+
+    let ranges = IpRanges::default()
+        .with_ranges("Googlebot", GOOGLE_RANGES)?
+        .with_token_ranges("Googlebot", "AdsBot-Google", GOOGLE_SPECIAL_CRAWLER_RANGES)?;
+    let detector = DeviceDetector::builder().verify_bots(ranges).build();
+
+    assert_eq!(
+        verification(&detector, ADSBOT, "66.249.90.65")?,
+        Verification::Verified
+    );
+    assert_eq!(
+        verification(&detector, ADSBOT, "66.249.66.1")?,
+        Verification::Spoofed
+    );
+    assert_eq!(
+        verification(&detector, GOOGLEBOT, "66.249.90.65")?,
+        Verification::Spoofed
+    );
+
+    assert!(IpRanges::default()
+        .with_ranges(
+            "Googlebot",
+            r#"{"prefixes": [{"ipv4Prefix": "66.249.64.0/33"}]}"#
+        )
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_reverse_dns() -> Result<()> {
+    let resolver = StubResolver::default()
+        .host("66.249.66.1", "crawl-66-249-66-1.googlebot.com")
+        .host("157.55.39.1", "msnbot-157-55-39-1.search.msn.com")
+        .ptr("203.0.113.7", "crawl-203-0-113-7.googlebot.com")
+        .host("203.0.113.8", "googlebot.com.example.net");
+    let detector = DeviceDetector::builder()
+        .verify_bots(ReverseDns::new(resolver))
+        .build();
+
+    assert_eq!(
+        verification(&detector, GOOGLEBOT, "66.249.66.1")?,
+        Verification::Verified
+    );
+    assert_eq!(
+        verification(&detector, BINGBOT, "157.55.39.1")?,
+        Verification::Verified
+    );
+    // the forward lookup doesn't lead back to the ip.
+    assert_eq!(
+        verification(&detector, GOOGLEBOT, "203.0.113.7")?,
+        Verification::Spoofed
+    );
+    assert_eq!(
+        verification(&detector, GOOGLEBOT, "203.0.113.8")?,
+        Verification::Spoofed
+    );
+    assert_eq!(
+        verification(&detector, GOOGLEBOT, "198.51.100.1")?,
+        Verification::Spoofed
+    );
+    // a failed lookup proves nothing.
+    assert_eq!(
+        verification(&detector, GOOGLEBOT, "127.0.0.1")?,
+        Verification::Unknown
+    );
+    assert_eq!(
+        verification(&detector, GPTBOT, "20.15.240.79")?,
+        Verification::Unknown
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_verifier_order() -> Result<()> {
+    // ranges tell nothing of bing, so reverse dns gets to.
+    let detector = DeviceDetector::builder()
+        .verify_bots(IpRanges::default().with_ranges("Googlebot", GOOGLE_RANGES)?)
+        .verify_bots(ReverseDns::new(
+            StubResolver::default().host("157.55.39.1", "msnbot-157-55-39-1.search.msn.com"),
+        ))
+        .build();
+
+    assert_eq!(
+        verification(&detector, BINGBOT, "157.55.39.1")?,
+        Verification::Verified
+    );
+    assert_eq!(
+        verification(&detector, GOOGLEBOT, "157.55.39.1")?,
+        Verification::Spoofed
+    );
+
+    Ok(())
+}